@@ -1,18 +1,93 @@
 use crate::{error::TokenError, storage};
-use soroban_sdk::{panic_with_error, Address, Env};
+use sep_41_token::AllowanceInfo;
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
 
+/// Set the allowance of `spender` from `from`
+///
+/// Returns false if the allowance was already set to `amount` and `expiration_ledger`,
+/// in which case nothing is written
 pub fn create_allowance(
     e: &Env,
     from: &Address,
     spender: &Address,
     amount: i128,
     expiration_ledger: u32,
-) {
+) -> bool {
     if amount > 0 && expiration_ledger < e.ledger().sequence() {
         panic_with_error!(e, TokenError::AllowanceError);
     }
+    let existing = storage::get_allowance(e, from, spender);
+    if existing.amount == amount && existing.expiration_ledger == expiration_ledger {
+        return false;
+    }
+
+    if amount > 0 {
+        storage::set_allowance(e, from, spender, amount, expiration_ledger);
+        let mut spenders = storage::get_spenders(e, from);
+        if !spenders.contains(spender) {
+            spenders.push_back(spender.clone());
+            storage::set_spenders(e, from, &spenders);
+            storage::set_approval_count(e, storage::get_approval_count(e) + 1);
+        }
+    } else if storage::clean_zero_allowance(e, from, spender) {
+        storage::set_approval_count(e, storage::get_approval_count(e).saturating_sub(1));
+    }
+    true
+}
+
+/// Revoke every non-zero allowance granted by `from`.
+///
+/// Returns the spenders whose allowance was revoked.
+pub fn revoke_all_allowances(e: &Env, from: &Address) -> Vec<Address> {
+    let spenders = storage::get_spenders(e, from);
+    let mut revoked = Vec::new(e);
+    for spender in spenders.iter() {
+        if storage::get_allowance(e, from, &spender).amount > 0 {
+            revoked.push_back(spender.clone());
+        }
+        storage::remove_allowance(e, from, &spender);
+    }
+    storage::set_spenders(e, from, &Vec::new(e));
+    storage::set_approval_count(
+        e,
+        storage::get_approval_count(e).saturating_sub(spenders.len() as u64),
+    );
+    revoked
+}
+
+/// Count the non-zero allowances granted by `from` that have not expired
+pub fn read_approval_count(e: &Env, from: &Address) -> u64 {
+    let mut count = 0;
+    for spender in storage::get_spenders(e, from).iter() {
+        if storage::get_allowance(e, from, &spender).amount > 0 {
+            count += 1;
+        }
+    }
+    count
+}
 
-    storage::set_allowance(e, from, spender, amount, expiration_ledger);
+/// Read the page of size `page_size` at index `page` of the allowances granted by `from`
+pub fn read_allowance_page(
+    e: &Env,
+    from: &Address,
+    page: u32,
+    page_size: u32,
+) -> Vec<(Address, AllowanceInfo)> {
+    let spenders = storage::get_spenders(e, from);
+    let start = page.saturating_mul(page_size).min(spenders.len());
+    let end = start.saturating_add(page_size).min(spenders.len());
+    let mut allowances = Vec::new(e);
+    for spender in spenders.slice(start..end).iter() {
+        let allowance = storage::get_allowance(e, from, &spender);
+        allowances.push_back((
+            spender,
+            AllowanceInfo {
+                amount: allowance.amount,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+        ));
+    }
+    allowances
 }
 
 pub fn spend_allowance(e: &Env, from: &Address, spender: &Address, amount: i128) {
@@ -21,10 +96,47 @@ pub fn spend_allowance(e: &Env, from: &Address, spender: &Address, amount: i128)
         panic_with_error!(e, TokenError::AllowanceError);
     }
     if amount > 0 {
-        storage::set_allowance(
+        let remaining = allowance.amount - amount;
+        if remaining > 0 {
+            storage::set_allowance(e, from, spender, remaining, allowance.expiration_ledger);
+        } else if storage::clean_zero_allowance(e, from, spender) {
+            storage::set_approval_count(e, storage::get_approval_count(e).saturating_sub(1));
+        }
+    }
+}
+
+pub fn create_recipient_allowance(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    to: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+) {
+    if amount > 0 && expiration_ledger < e.ledger().sequence() {
+        panic_with_error!(e, TokenError::AllowanceError);
+    }
+
+    storage::set_recipient_allowance(e, from, spender, to, amount, expiration_ledger);
+}
+
+pub fn spend_recipient_allowance(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    to: &Address,
+    amount: i128,
+) {
+    let allowance = storage::get_recipient_allowance(e, from, spender, to);
+    if allowance.amount < amount || e.ledger().sequence() > allowance.expiration_ledger {
+        panic_with_error!(e, TokenError::AllowanceError);
+    }
+    if amount > 0 {
+        storage::set_recipient_allowance(
             e,
             from,
             spender,
+            to,
             allowance.amount - amount,
             allowance.expiration_ledger,
         );
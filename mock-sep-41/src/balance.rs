@@ -1,15 +1,72 @@
-use crate::{error::TokenError, storage};
+use crate::{
+    error::TokenError, permissions::require_permission, stats::record_balance_change, storage,
+};
+use sep_41_token::TokenAuthorizationPolicy;
 use soroban_sdk::{panic_with_error, Address, Env};
 
 pub fn receive_balance(e: &Env, address: &Address, amount: i128) {
+    require_authorized(e, address);
+    require_permission(e, address, |perms| perms.can_receive);
     let balance = storage::get_balance(e, address);
     storage::set_balance(e, address, &(balance + amount));
+    record_balance_change(e, address, balance, balance + amount);
 }
 
 pub fn spend_balance(e: &Env, address: &Address, amount: i128) {
+    require_authorized(e, address);
+    if read_spendable_balance(e, address) < amount {
+        panic_with_error!(e, TokenError::BalanceError);
+    }
+    clawback_balance(e, address, amount);
+}
+
+/// Fetch the balance of `address` above its minimum balance
+pub fn read_spendable_balance(e: &Env, address: &Address) -> i128 {
+    storage::get_balance(e, address) - storage::get_min_balance(e, address)
+}
+
+/// Transfer `amount` out of the balance of `address`
+pub fn send_balance(e: &Env, address: &Address, amount: i128) {
+    require_permission(e, address, |perms| perms.can_send);
+    spend_balance(e, address, amount);
+}
+
+/// Burn `amount` from the balance of `address`
+pub fn burn_balance(e: &Env, address: &Address, amount: i128) {
+    require_permission(e, address, |perms| perms.can_burn);
+    spend_balance(e, address, amount);
+}
+
+/// Spend `amount` from `address` on behalf of the admin, regardless of the
+/// authorization status of `address`
+pub fn clawback_balance(e: &Env, address: &Address, amount: i128) {
     let balance = storage::get_balance(e, address);
     if balance < amount {
         panic_with_error!(e, TokenError::BalanceError);
     }
     storage::set_balance(e, address, &(balance - amount));
+    record_balance_change(e, address, balance, balance - amount);
+}
+
+/// Check if `address` can send and receive the token under the authorization policy
+pub fn is_authorized(e: &Env, address: &Address) -> bool {
+    storage::get_authorization_policy(e) == TokenAuthorizationPolicy::Open
+        || read_authorized_flag(e, address)
+}
+
+/// Fetch the authorization status set for `address`. Addresses are authorized by
+/// default, unless the policy requires authorization.
+pub fn read_authorized_flag(e: &Env, address: &Address) -> bool {
+    match storage::get_authorization_policy(e) {
+        TokenAuthorizationPolicy::AuthorizationRequired => {
+            storage::get_explicit_authorization(e, address).unwrap_or(false)
+        }
+        _ => storage::get_authorized(e, address),
+    }
+}
+
+pub fn require_authorized(e: &Env, address: &Address) {
+    if !is_authorized(e, address) {
+        panic_with_error!(e, TokenError::UnauthorizedError);
+    }
 }
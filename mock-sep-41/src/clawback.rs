@@ -0,0 +1,27 @@
+use crate::{balance::clawback_balance, storage};
+use sep_41_token::{ClawbackEntry, DEFAULT_CLAWBACK_LOG_SIZE};
+use soroban_sdk::{Address, Env, Vec};
+
+/// Claw back `amount` from `from`, recording it in the clawback log of `from`
+pub fn clawback(e: &Env, admin: &Address, from: &Address, amount: i128) {
+    clawback_balance(e, from, amount);
+
+    let mut log = storage::get_clawback_log(e, from);
+    log.push_front(ClawbackEntry {
+        ledger: e.ledger().sequence(),
+        amount,
+        by_admin: admin.clone(),
+    });
+    while log.len() > DEFAULT_CLAWBACK_LOG_SIZE {
+        log.pop_back();
+    }
+    storage::set_clawback_log(e, from, &log);
+}
+
+/// Read the page of size `page_size` at index `page` of the clawback log of `id`
+pub fn read_clawback_page(e: &Env, id: &Address, page: u32, page_size: u32) -> Vec<ClawbackEntry> {
+    let log = storage::get_clawback_log(e, id);
+    let start = page.saturating_mul(page_size).min(log.len());
+    let end = start.saturating_add(page_size).min(log.len());
+    log.slice(start..end)
+}
@@ -1,9 +1,61 @@
-use crate::allowance::{create_allowance, spend_allowance};
-use crate::balance::{receive_balance, spend_balance};
+use crate::allowance::{
+    create_allowance, create_recipient_allowance, read_allowance_page, read_approval_count,
+    revoke_all_allowances, spend_allowance, spend_recipient_allowance,
+};
+use crate::balance::{
+    burn_balance, is_authorized, read_authorized_flag, read_spendable_balance, receive_balance,
+    require_authorized, send_balance,
+};
+use crate::clawback::{clawback, read_clawback_page};
 use crate::error::TokenError;
-use crate::storage::{self, TokenMetadata};
-use sep_41_token::{Token, TokenEvents};
-use soroban_sdk::{contract, contractimpl, panic_with_error, Address, Env, String};
+use crate::escrow::{set_escrowed_allowance, spend_escrowed_allowance};
+use crate::fee::{
+    burn_on_transfer, calc_fee, charge_burn_fee, charge_transfer_fee, read_transfer_fee,
+    MAX_AUTO_BURN_BPS, MAX_BURN_FEE_BPS, MAX_FEE_BPS,
+};
+use crate::hold::{accept_hold, create_hold, expire_hold, reject_hold};
+use crate::limits::{
+    check_account_transfer_limit, check_minimum_transfer, check_transfer_limits,
+    read_account_transfer_volume, use_account_transfer_limit,
+};
+use crate::migration::{migrate_storage, STORAGE_VERSION};
+use crate::permissions::require_permission;
+use crate::snapshot::{read_snapshot, take_snapshot};
+use crate::stake::{
+    create_stake, harvest_rewards, read_pending_rewards, read_staked_balance, read_voting_power,
+    release_stake, set_reward_rate,
+};
+use crate::stats::{read_holder_page, record_burn, record_mint, record_transfer};
+use crate::storage::{
+    self, AccountTransferLimit, TokenMetadata, TransferFee, AIRDROP_PERIOD_LEDGERS,
+};
+use sep_41_token::{
+    auth::read_nonce,
+    oracle::OracleConditionClient,
+    receiver::TokenReceiverClient,
+    utils::set_authorized_idempotent,
+    validate::{
+        require_decimals_mutable, validate_decimals, validate_not_zero_address,
+        validate_string_utf8,
+    },
+    AdminToken, AllowanceInfo, AssetLinkedToken, AuthorizationPolicyToken, AutoBurnToken,
+    BurnAndReplaceToken, CappedToken, ClaimMintToken, ClawbackEntry, ClawbackLogToken,
+    ConditionalBurnToken, DetailedTransferToken, EnumerableAllowanceToken, EnumerableHoldersToken,
+    EscrowedAllowanceToken, ExtendableAllowanceToken, FeeToken, HoldTransferToken,
+    LargeTransferToken, LockedSupplyToken, MigratableToken, MinBalanceToken, NonceToken,
+    NotifyingToken, PeriodicAirdropToken, PermissionedToken, RecipientAllowanceToken,
+    SafeApproveToken, SnapshotToken, StakingRewardsToken, StakingToken, StatsToken,
+    StellarAssetClient, Token, TokenAnalytics, TokenAuthorizationPolicy, TokenEvents,
+    TokenPermissions, TokenStats, TransferLimitsToken, TransferLogToken, TransferReceipt,
+    VersionedToken, MAX_TOKEN_NAME_LEN, MAX_TOKEN_SYMBOL_LEN,
+};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, Address, Bytes, Env, Executable,
+    String, Vec,
+};
+
+/// The maximum number of entries in a single batch call
+const MAX_BATCH_SIZE: u32 = 100;
 
 fn check_nonnegative_amount(amount: i128) {
     if amount < 0 {
@@ -11,6 +63,62 @@ fn check_nonnegative_amount(amount: i128) {
     }
 }
 
+/// Move `amount` from `from` to `to`, charging any transfer fee and auto burn
+///
+/// Returns the amount received by `to`
+fn transfer_balance(e: &Env, from: Address, to: Address, amount: i128) -> i128 {
+    if storage::get_authorization_policy(e) == TokenAuthorizationPolicy::AdminControlled {
+        storage::get_admin(e).require_auth();
+    }
+    check_transfer_limits(e, amount);
+    use_account_transfer_limit(e, &from, amount);
+    send_balance(e, &from, amount);
+    let fee = charge_transfer_fee(e, &from, &to, amount);
+    let burn_amount = burn_on_transfer(e, &from, amount);
+    let net_amount = amount - fee - burn_amount;
+    receive_balance(e, &to, net_amount);
+    record_transfer(e);
+    storage::set_last_transfer(e, &from, &to, net_amount);
+
+    TokenEvents::transfer(e, from.clone(), to.clone(), net_amount);
+    if storage::get_large_transfer_threshold(e).is_some_and(|threshold| amount >= threshold) {
+        TokenEvents::large_transfer(e, from, to, amount);
+    }
+    net_amount
+}
+
+/// The extension configuration applied by `initialize_with_config`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MockTokenConfig {
+    /// The maximum total supply of the token
+    pub cap: i128,
+    /// The transfer fee in basis points, paid to the admin
+    pub fee_bps: u32,
+    /// The addresses to deauthorize
+    pub blacklist: Vec<Address>,
+}
+
+/// The configuration and statistics of the token, returned by `full_snapshot`
+#[cfg(feature = "debug")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MockTokenState {
+    pub admin: Address,
+    pub decimals: u32,
+    pub name: String,
+    pub symbol: String,
+    pub stats: TokenStats,
+    /// The maximum total supply of the token
+    pub cap: i128,
+    /// The transfer fee in basis points, and the address it is paid to
+    pub fee_bps: u32,
+    pub fee_recipient: Option<Address>,
+    /// The auto burn rate in basis points
+    pub auto_burn_bps: u32,
+    pub storage_version: u32,
+}
+
 #[contract]
 pub struct MockToken;
 
@@ -20,9 +128,13 @@ impl MockToken {
         if storage::has_admin(&e) {
             panic_with_error!(e, TokenError::AlreadyInitializedError);
         }
+        validate_decimals(&e, decimal, TokenError::DecimalsTooLargeError);
         if decimal > 27 {
             panic_with_error!(e, TokenError::OperationNotSupportedError);
         }
+        if !validate_string_utf8(&name) || !validate_string_utf8(&symbol) {
+            panic_with_error!(e, TokenError::InvalidMetadataError);
+        }
         storage::set_admin(&e, &admin);
         let metadata = TokenMetadata {
             decimal,
@@ -30,6 +142,45 @@ impl MockToken {
             symbol,
         };
         storage::set_metadata(&e, &metadata);
+        storage::set_storage_version(&e, STORAGE_VERSION);
+        storage::set_genesis_ledger(&e, e.ledger().sequence());
+    }
+
+    /// Initialize the token and apply `config` in a single call. Panics if the cap is
+    /// negative or the fee exceeds 100%.
+    pub fn initialize_with_config(
+        e: Env,
+        admin: Address,
+        decimal: u32,
+        name: String,
+        symbol: String,
+        config: MockTokenConfig,
+    ) {
+        Self::initialize(e.clone(), admin.clone(), decimal, name, symbol);
+        if config.cap < 0 || config.fee_bps > MAX_FEE_BPS {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+
+        storage::set_cap(&e, config.cap);
+        if config.fee_bps > 0 {
+            storage::set_transfer_fee(
+                &e,
+                &TransferFee {
+                    fee_bps: config.fee_bps,
+                    recipient: admin.clone(),
+                },
+            );
+        }
+        for id in config.blacklist.iter() {
+            set_authorized_idempotent(
+                &e,
+                &admin,
+                &id,
+                read_authorized_flag(&e, &id),
+                false,
+                storage::set_authorized,
+            );
+        }
     }
 
     pub fn mint(e: Env, to: Address, amount: i128) {
@@ -39,10 +190,52 @@ impl MockToken {
         storage::extend_instance(&e);
 
         receive_balance(&e, &to, amount);
+        record_mint(&e, amount);
 
         TokenEvents::mint(&e, admin, to, amount);
     }
 
+    /// Mint each `(to, amount)` in `recipients`. Panics if the batch contains more than
+    /// 100 entries, or the total supply would exceed the cap.
+    pub fn mint_to_many(e: Env, admin: Address, recipients: Vec<(Address, i128)>) {
+        if admin != storage::get_admin(&e) {
+            panic_with_error!(e, TokenError::UnauthorizedError);
+        }
+        admin.require_auth();
+        if recipients.len() > MAX_BATCH_SIZE {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        for (to, amount) in recipients.iter() {
+            check_nonnegative_amount(amount);
+            receive_balance(&e, &to, amount);
+            record_mint(&e, amount);
+
+            TokenEvents::mint(&e, admin.clone(), to, amount);
+        }
+    }
+
+    /// Set the decimals of the token. Panics if any tokens are in circulation.
+    pub fn set_decimals(e: Env, decimals: u32) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        require_decimals_mutable(
+            &e,
+            storage::get_stats(&e).total_supply,
+            TokenError::DecimalsImmutableAfterMintError,
+        );
+        validate_decimals(&e, decimals, TokenError::DecimalsTooLargeError);
+        if decimals > 27 {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        let mut metadata = storage::get_metadata(&e);
+        metadata.decimal = decimals;
+        storage::set_metadata(&e, &metadata);
+    }
+
     pub fn set_admin(e: Env, new_admin: Address) {
         let admin = storage::get_admin(&e);
         admin.require_auth();
@@ -50,6 +243,176 @@ impl MockToken {
 
         storage::set_admin(&e, &new_admin);
     }
+
+    pub fn set_authorized(e: Env, id: Address, authorize: bool) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        set_authorized_idempotent(
+            &e,
+            &admin,
+            &id,
+            read_authorized_flag(&e, &id),
+            authorize,
+            storage::set_authorized,
+        );
+    }
+
+    /// Set the authorization status of multiple addresses. Panics if the batch
+    /// contains more than 100 entries or includes the admin or a zero address.
+    pub fn bulk_set_authorized(e: Env, updates: Vec<(Address, bool)>) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if updates.len() > MAX_BATCH_SIZE {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        for (id, authorize) in updates.iter() {
+            if id == admin {
+                panic_with_error!(e, TokenError::UnauthorizedError);
+            }
+            validate_not_zero_address(&e, &id, TokenError::UnauthorizedError);
+            set_authorized_idempotent(
+                &e,
+                &admin,
+                &id,
+                read_authorized_flag(&e, &id),
+                authorize,
+                storage::set_authorized,
+            );
+        }
+    }
+
+    pub fn authorized(e: Env, id: Address) -> bool {
+        is_authorized(&e, &id)
+    }
+
+    pub fn clawback(e: Env, from: Address, amount: i128) {
+        check_nonnegative_amount(amount);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        clawback(&e, &admin, &from, amount);
+        record_burn(&e, amount);
+
+        TokenEvents::clawback(&e, admin, from, amount);
+    }
+
+    /// Claw back each `(from, amount)` in `burns`. Panics if the batch contains more
+    /// than 100 entries.
+    pub fn batch_burn(e: Env, burns: Vec<(Address, i128)>) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if burns.len() > MAX_BATCH_SIZE {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        for (from, amount) in burns.iter() {
+            check_nonnegative_amount(amount);
+            clawback(&e, &admin, &from, amount);
+            record_burn(&e, amount);
+
+            TokenEvents::clawback(&e, admin.clone(), from, amount);
+        }
+    }
+
+    /// Transfer each `(from, amount)` in `froms` to `to`, consuming the allowance of
+    /// `spender` for each `from`. Emits a `transfer` event per sender.
+    pub fn transfer_many_to_one(
+        e: Env,
+        spender: Address,
+        froms: Vec<(Address, i128)>,
+        to: Address,
+    ) {
+        spender.require_auth();
+        storage::extend_instance(&e);
+
+        for (from, amount) in froms.iter() {
+            check_nonnegative_amount(amount);
+            spend_allowance(&e, &from, &spender, amount);
+            transfer_balance(&e, from, to.clone(), amount);
+        }
+    }
+
+    /// Validate a transfer of `amount` from `from` to `to` without executing it, and
+    /// return the resulting balances of `from` and `to`, net of any transfer fee and
+    /// auto burn.
+    ///
+    /// Panics with the error the transfer would fail with. The admin authorization a
+    /// transfer requires under `TokenAuthorizationPolicy::AdminControlled` is not
+    /// simulated.
+    pub fn simulate_balance_after_transfer(
+        e: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> (i128, i128) {
+        check_nonnegative_amount(amount);
+        check_transfer_limits(&e, amount);
+        check_account_transfer_limit(&e, &from, amount);
+        require_authorized(&e, &from);
+        require_authorized(&e, &to);
+        require_permission(&e, &from, |perms| perms.can_send);
+        require_permission(&e, &to, |perms| perms.can_receive);
+
+        if read_spendable_balance(&e, &from) < amount {
+            panic_with_error!(e, TokenError::BalanceError);
+        }
+        let mut from_balance = storage::get_balance(&e, &from);
+        let mut to_balance = storage::get_balance(&e, &to);
+        let mut net_amount = amount - calc_fee(amount, storage::get_auto_burn_rate(&e));
+        if let Some((recipient, fee_amount)) = read_transfer_fee(&e, &from, &to, amount) {
+            net_amount -= fee_amount;
+            if recipient == from {
+                from_balance += fee_amount;
+            }
+            if recipient == to {
+                to_balance += fee_amount;
+            }
+        }
+        if from == to {
+            let balance = from_balance - amount + net_amount;
+            return (balance, balance);
+        }
+        (from_balance - amount, to_balance + net_amount)
+    }
+
+    /// Revoke every non-zero allowance granted by `from`
+    pub fn revoke_all_allowances(e: Env, from: Address) {
+        from.require_auth();
+        storage::extend_instance(&e);
+
+        for spender in revoke_all_allowances(&e, &from).iter() {
+            TokenEvents::approve(&e, from.clone(), spender, 0, 0);
+        }
+    }
+}
+
+#[cfg(feature = "debug")]
+#[contractimpl]
+impl MockToken {
+    /// Returns the configuration and statistics of the token in a single call, for
+    /// debugging a deployment. Only compiled with the `debug` feature.
+    pub fn full_snapshot(e: Env) -> MockTokenState {
+        let metadata = storage::get_metadata(&e);
+        let fee = storage::get_transfer_fee(&e);
+        MockTokenState {
+            admin: storage::get_admin(&e),
+            decimals: metadata.decimal,
+            name: metadata.name,
+            symbol: metadata.symbol,
+            stats: storage::get_stats(&e),
+            cap: storage::get_cap(&e),
+            fee_bps: fee.as_ref().map_or(0, |fee| fee.fee_bps),
+            fee_recipient: fee.map(|fee| fee.recipient),
+            auto_burn_bps: storage::get_auto_burn_rate(&e),
+            storage_version: storage::get_storage_version(&e),
+        }
+    }
 }
 
 #[contractimpl]
@@ -64,9 +427,10 @@ impl Token for MockToken {
         check_nonnegative_amount(amount);
         storage::extend_instance(&e);
 
-        create_allowance(&e, &from, &spender, amount, expiration_ledger);
-
-        TokenEvents::approve(&e, from, spender, amount, expiration_ledger);
+        require_permission(&e, &from, |perms| perms.can_approve);
+        if create_allowance(&e, &from, &spender, amount, expiration_ledger) {
+            TokenEvents::approve(&e, from, spender, amount, expiration_ledger);
+        }
     }
 
     fn balance(e: Env, id: Address) -> i128 {
@@ -79,10 +443,7 @@ impl Token for MockToken {
         check_nonnegative_amount(amount);
         storage::extend_instance(&e);
 
-        spend_balance(&e, &from, amount);
-        receive_balance(&e, &to, amount);
-
-        TokenEvents::transfer(&e, from, to, amount);
+        transfer_balance(&e, from, to, amount);
     }
 
     fn transfer_from(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
@@ -91,10 +452,7 @@ impl Token for MockToken {
         storage::extend_instance(&e);
 
         spend_allowance(&e, &from, &spender, amount);
-        spend_balance(&e, &from, amount);
-        receive_balance(&e, &to, amount);
-
-        TokenEvents::transfer(&e, from, to, amount);
+        transfer_balance(&e, from, to, amount);
     }
 
     fn burn(e: Env, from: Address, amount: i128) {
@@ -102,9 +460,12 @@ impl Token for MockToken {
         check_nonnegative_amount(amount);
         storage::extend_instance(&e);
 
-        spend_balance(&e, &from, amount);
+        check_minimum_transfer(&e, amount);
+        burn_balance(&e, &from, amount);
+        let burn_amount = amount - charge_burn_fee(&e, &from, amount);
+        record_burn(&e, burn_amount);
 
-        TokenEvents::burn(&e, from, amount);
+        TokenEvents::burn(&e, from, burn_amount);
     }
 
     fn burn_from(e: Env, spender: Address, from: Address, amount: i128) {
@@ -113,9 +474,12 @@ impl Token for MockToken {
         storage::extend_instance(&e);
 
         spend_allowance(&e, &from, &spender, amount);
-        spend_balance(&e, &from, amount);
+        check_minimum_transfer(&e, amount);
+        burn_balance(&e, &from, amount);
+        let burn_amount = amount - charge_burn_fee(&e, &from, amount);
+        record_burn(&e, burn_amount);
 
-        TokenEvents::burn(&e, from, amount);
+        TokenEvents::burn(&e, from, burn_amount);
     }
 
     fn decimals(e: Env) -> u32 {
@@ -130,3 +494,810 @@ impl Token for MockToken {
         storage::get_metadata(&e).symbol
     }
 }
+
+#[contractimpl]
+impl SnapshotToken for MockToken {
+    fn take_balance_snapshot(e: Env, id: Address) {
+        storage::extend_instance(&e);
+        take_snapshot(&e, &id);
+    }
+
+    fn snapshot_balance(e: Env, id: Address) -> i128 {
+        read_snapshot(&e, &id)
+    }
+}
+
+#[contractimpl]
+impl FeeToken for MockToken {
+    fn transfer_fee_bps(e: Env) -> u32 {
+        storage::get_transfer_fee(&e).map_or(0, |fee| fee.fee_bps)
+    }
+
+    fn fee_recipient(e: Env) -> Option<Address> {
+        storage::get_transfer_fee(&e).map(|fee| fee.recipient)
+    }
+
+    fn transfer_fee_estimate(e: Env, from: Address, to: Address, amount: i128) -> i128 {
+        check_nonnegative_amount(amount);
+        read_transfer_fee(&e, &from, &to, amount).map_or(0, |(_, fee_amount)| fee_amount)
+    }
+
+    fn set_transfer_fee(e: Env, fee_bps: u32, recipient: Address) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if fee_bps + storage::get_auto_burn_rate(&e) > MAX_FEE_BPS {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        storage::set_transfer_fee(&e, &TransferFee { fee_bps, recipient });
+    }
+
+    fn burn_fee_bps(e: Env) -> u32 {
+        storage::get_burn_fee_bps(&e)
+    }
+
+    fn set_burn_fee_bps(e: Env, fee_bps: u32) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if fee_bps > MAX_BURN_FEE_BPS {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        storage::set_burn_fee_bps(&e, fee_bps);
+    }
+
+    fn add_fee_exempt(e: Env, id: Address) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        storage::set_fee_exempt(&e, &id, true);
+
+        TokenEvents::fee_exempt_added(&e, admin, id);
+    }
+
+    fn remove_fee_exempt(e: Env, id: Address) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        storage::set_fee_exempt(&e, &id, false);
+
+        TokenEvents::fee_exempt_removed(&e, admin, id);
+    }
+
+    fn is_fee_exempt(e: Env, id: Address) -> bool {
+        storage::is_fee_exempt(&e, &id)
+    }
+}
+
+#[contractimpl]
+impl TransferLimitsToken for MockToken {
+    fn minimum_transfer(e: Env) -> i128 {
+        storage::get_minimum_transfer(&e)
+    }
+
+    fn set_minimum_transfer(e: Env, min: i128) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if min <= 0 || min > storage::get_maximum_transfer(&e) {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        storage::set_minimum_transfer(&e, min);
+    }
+
+    fn maximum_transfer(e: Env) -> i128 {
+        storage::get_maximum_transfer(&e)
+    }
+
+    fn set_maximum_transfer(e: Env, max: i128) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if max < storage::get_minimum_transfer(&e) {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        storage::set_maximum_transfer(&e, max);
+    }
+
+    fn set_account_transfer_limit(e: Env, id: Address, limit: i128, window_ledgers: u32) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if limit < 0 || window_ledgers == 0 {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        storage::set_account_transfer_limit(
+            &e,
+            &id,
+            &AccountTransferLimit {
+                limit,
+                window_ledgers,
+                window_start: e.ledger().sequence(),
+                volume: 0,
+            },
+        );
+    }
+
+    fn account_transfer_volume(e: Env, id: Address) -> i128 {
+        read_account_transfer_volume(&e, &id)
+    }
+}
+
+#[contractimpl]
+impl ClaimMintToken for MockToken {
+    fn allocate(e: Env, to: Address, amount: i128) {
+        check_nonnegative_amount(amount);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        let allocation = storage::get_allocation(&e, &to);
+        storage::set_allocation(&e, &to, allocation + amount);
+    }
+
+    fn claim(e: Env, id: Address) {
+        id.require_auth();
+        storage::extend_instance(&e);
+
+        let amount = storage::get_allocation(&e, &id);
+        if amount > 0 {
+            storage::set_allocation(&e, &id, 0);
+            receive_balance(&e, &id, amount);
+            record_mint(&e, amount);
+
+            TokenEvents::mint(&e, storage::get_admin(&e), id, amount);
+        }
+    }
+
+    fn claimable(e: Env, id: Address) -> i128 {
+        storage::get_allocation(&e, &id)
+    }
+}
+
+#[contractimpl]
+impl PeriodicAirdropToken for MockToken {
+    fn register_airdrop_recipient(e: Env, id: Address, amount_per_period: i128) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if amount_per_period <= 0 {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        storage::set_airdrop_amount(&e, &id, amount_per_period);
+    }
+
+    fn claim_airdrop(e: Env, id: Address) {
+        id.require_auth();
+        storage::extend_instance(&e);
+
+        let amount = match storage::get_airdrop_amount(&e, &id) {
+            Some(amount) => amount,
+            None => panic_with_error!(e, TokenError::AirdropNotFoundError),
+        };
+        if let Some(last_claim) = storage::get_last_airdrop_claim(&e, &id) {
+            if e.ledger().sequence() < last_claim + AIRDROP_PERIOD_LEDGERS {
+                panic_with_error!(e, TokenError::AirdropNotClaimableError);
+            }
+        }
+        storage::set_last_airdrop_claim(&e, &id);
+        receive_balance(&e, &id, amount);
+        record_mint(&e, amount);
+
+        TokenEvents::mint(&e, storage::get_admin(&e), id, amount);
+    }
+
+    fn deregister_airdrop_recipient(e: Env, id: Address) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        storage::remove_airdrop_amount(&e, &id);
+    }
+
+    fn airdrop_period_length(_e: Env) -> u32 {
+        AIRDROP_PERIOD_LEDGERS
+    }
+}
+
+#[contractimpl]
+impl BurnAndReplaceToken for MockToken {
+    fn replacement_token(e: Env) -> Option<Address> {
+        storage::get_replacement_token(&e)
+    }
+
+    fn set_replacement_token(e: Env, new_token: Address) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        storage::set_replacement_token(&e, &new_token);
+    }
+
+    fn migrate(e: Env, from: Address, amount: i128) {
+        from.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        let new_token = match storage::get_replacement_token(&e) {
+            Some(new_token) => new_token,
+            None => panic_with_error!(e, TokenError::OperationNotSupportedError),
+        };
+        check_minimum_transfer(&e, amount);
+        burn_balance(&e, &from, amount);
+        record_burn(&e, amount);
+        StellarAssetClient::new(&e, &new_token).mint(&from, &amount);
+
+        TokenEvents::burn(&e, from.clone(), amount);
+        TokenEvents::migrate(&e, from, new_token, amount);
+    }
+}
+
+#[contractimpl]
+impl NotifyingToken for MockToken {
+    fn transfer_and_notify(e: Env, from: Address, to: Address, amount: i128, data: Bytes) {
+        from.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        transfer_balance(&e, from.clone(), to.clone(), amount);
+
+        if let Some(Executable::Wasm(_)) = to.executable() {
+            TokenReceiverClient::new(&e, &to).on_receive(&from, &amount, &data);
+        }
+    }
+}
+
+#[contractimpl]
+impl EnumerableAllowanceToken for MockToken {
+    fn allowance_pages(
+        e: Env,
+        from: Address,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<(Address, AllowanceInfo)> {
+        read_allowance_page(&e, &from, page, page_size)
+    }
+
+    fn approval_count(e: Env, from: Address) -> u64 {
+        read_approval_count(&e, &from)
+    }
+
+    fn active_approvals(e: Env) -> u64 {
+        storage::get_approval_count(&e)
+    }
+}
+
+#[contractimpl]
+impl RecipientAllowanceToken for MockToken {
+    fn recipient_allowance(e: Env, from: Address, spender: Address, to: Address) -> i128 {
+        storage::get_recipient_allowance(&e, &from, &spender, &to).amount
+    }
+
+    fn approve_to(
+        e: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        amount: i128,
+        live_until_ledger: u32,
+    ) {
+        from.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        require_permission(&e, &from, |perms| perms.can_approve);
+        create_recipient_allowance(&e, &from, &spender, &to, amount, live_until_ledger);
+
+        TokenEvents::approve_to(&e, from, spender, to, amount, live_until_ledger);
+    }
+
+    fn transfer_from_to(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        spend_recipient_allowance(&e, &from, &spender, &to, amount);
+        transfer_balance(&e, from, to, amount);
+    }
+}
+
+#[contractimpl]
+impl EscrowedAllowanceToken for MockToken {
+    fn escrowed_allowance(e: Env, from: Address, spender: Address) -> AllowanceInfo {
+        let escrow = storage::get_escrow(&e, &from, &spender);
+        AllowanceInfo {
+            amount: escrow.amount,
+            expiration_ledger: escrow.expiration_ledger,
+        }
+    }
+
+    fn approve_escrowed(
+        e: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        live_until_ledger: u32,
+    ) {
+        from.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        require_permission(&e, &from, |perms| perms.can_approve);
+        let delta = set_escrowed_allowance(&e, &from, &spender, amount, live_until_ledger);
+        if delta != 0 {
+            record_transfer(&e);
+        }
+
+        if delta > 0 {
+            TokenEvents::transfer(&e, from, e.current_contract_address(), delta);
+        } else if delta < 0 {
+            TokenEvents::transfer(&e, e.current_contract_address(), from, -delta);
+        }
+    }
+
+    fn transfer_from_escrowed(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        let net_amount = spend_escrowed_allowance(&e, &from, &spender, &to, amount);
+        record_transfer(&e);
+
+        TokenEvents::transfer(&e, e.current_contract_address(), to, net_amount);
+    }
+}
+
+#[contractimpl]
+impl StakingToken for MockToken {
+    fn stake(e: Env, from: Address, amount: i128, lock_until: u32) -> u64 {
+        from.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        let stake_id = create_stake(&e, &from, amount, lock_until);
+        record_transfer(&e);
+
+        TokenEvents::transfer(&e, from, e.current_contract_address(), amount);
+        stake_id
+    }
+
+    fn unstake(e: Env, stake_id: u64) {
+        storage::extend_instance(&e);
+
+        let stake = release_stake(&e, stake_id);
+        record_transfer(&e);
+
+        TokenEvents::transfer(&e, e.current_contract_address(), stake.owner, stake.amount);
+    }
+
+    fn staked_balance(e: Env, id: Address) -> i128 {
+        read_staked_balance(&e, &id)
+    }
+
+    fn voting_power(e: Env, id: Address) -> i128 {
+        read_voting_power(&e, &id)
+    }
+}
+
+#[contractimpl]
+impl StakingRewardsToken for MockToken {
+    fn set_reward_rate(e: Env, rate: i128) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if rate < 0 {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        set_reward_rate(&e, rate);
+    }
+
+    fn set_reward_pool(e: Env, amount: i128) {
+        check_nonnegative_amount(amount);
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        storage::set_reward_pool(&e, amount);
+    }
+
+    fn reward_pool(e: Env) -> i128 {
+        storage::get_reward_pool(&e)
+    }
+
+    fn pending_rewards(e: Env, staker: Address) -> i128 {
+        read_pending_rewards(&e, &staker)
+    }
+
+    fn harvest_rewards(e: Env, staker: Address) {
+        staker.require_auth();
+        storage::extend_instance(&e);
+
+        let amount = harvest_rewards(&e, &staker);
+        if amount > 0 {
+            record_mint(&e, amount);
+            TokenEvents::mint(&e, storage::get_admin(&e), staker, amount);
+        }
+    }
+}
+
+#[contractimpl]
+impl ConditionalBurnToken for MockToken {
+    fn conditional_burn(
+        e: Env,
+        from: Address,
+        amount: i128,
+        condition_oracle: Address,
+        condition_data: Bytes,
+    ) {
+        from.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        if !OracleConditionClient::new(&e, &condition_oracle).check_condition(&condition_data) {
+            panic_with_error!(e, TokenError::ConditionNotMetError);
+        }
+        check_minimum_transfer(&e, amount);
+        burn_balance(&e, &from, amount);
+        let burn_amount = amount - charge_burn_fee(&e, &from, amount);
+        record_burn(&e, burn_amount);
+
+        TokenEvents::burn(&e, from, burn_amount);
+    }
+}
+
+#[contractimpl]
+impl StatsToken for MockToken {
+    fn stats(e: Env) -> TokenStats {
+        storage::get_stats(&e)
+    }
+
+    fn total_burned(e: Env) -> i128 {
+        storage::get_stats(&e).total_burned
+    }
+}
+
+#[contractimpl]
+impl HoldTransferToken for MockToken {
+    fn hold_transfer(e: Env, from: Address, to: Address, amount: i128, expiry: u32) -> u64 {
+        from.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        let hold_id = create_hold(&e, &from, &to, amount, expiry);
+        record_transfer(&e);
+
+        TokenEvents::transfer(&e, from, e.current_contract_address(), amount);
+        hold_id
+    }
+
+    fn accept_hold(e: Env, hold_id: u64) {
+        storage::extend_instance(&e);
+
+        let (hold, net_amount) = accept_hold(&e, hold_id);
+        record_transfer(&e);
+
+        TokenEvents::transfer(&e, e.current_contract_address(), hold.to, net_amount);
+    }
+
+    fn reject_hold(e: Env, hold_id: u64) {
+        storage::extend_instance(&e);
+
+        let hold = reject_hold(&e, hold_id);
+        record_transfer(&e);
+
+        TokenEvents::transfer(&e, e.current_contract_address(), hold.from, hold.amount);
+    }
+
+    fn expire_hold(e: Env, hold_id: u64) {
+        storage::extend_instance(&e);
+
+        let hold = expire_hold(&e, hold_id);
+        record_transfer(&e);
+
+        TokenEvents::transfer(&e, e.current_contract_address(), hold.from, hold.amount);
+    }
+}
+
+#[contractimpl]
+impl PermissionedToken for MockToken {
+    fn set_permissions(e: Env, id: Address, perms: TokenPermissions) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        storage::set_permissions(&e, &id, &perms);
+    }
+
+    fn permissions(e: Env, id: Address) -> TokenPermissions {
+        storage::get_permissions(&e, &id)
+    }
+}
+
+#[contractimpl]
+impl DetailedTransferToken for MockToken {
+    fn transfer_detailed(e: Env, from: Address, to: Address, amount: i128) -> TransferReceipt {
+        from.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        let net_amount = transfer_balance(&e, from.clone(), to.clone(), amount);
+
+        TransferReceipt {
+            gross_amount: amount,
+            net_amount,
+            fee: amount - net_amount,
+            from_balance_after: storage::get_balance(&e, &from),
+            to_balance_after: storage::get_balance(&e, &to),
+        }
+    }
+}
+
+#[contractimpl(contracttrait)]
+impl CappedToken for MockToken {
+    fn cap(e: Env) -> i128 {
+        storage::get_cap(&e)
+    }
+
+    fn set_cap(e: Env, cap: i128) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if cap < storage::get_stats(&e).total_supply {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        storage::set_cap(&e, cap);
+    }
+
+    fn total_supply(e: Env) -> i128 {
+        storage::get_stats(&e).total_supply
+    }
+}
+
+#[contractimpl]
+impl AutoBurnToken for MockToken {
+    fn auto_burn_rate(e: Env) -> u32 {
+        storage::get_auto_burn_rate(&e)
+    }
+
+    fn set_auto_burn_rate(e: Env, burn_bps: u32) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        let fee_bps = storage::get_transfer_fee(&e).map_or(0, |fee| fee.fee_bps);
+        if burn_bps > MAX_AUTO_BURN_BPS || fee_bps + burn_bps > MAX_FEE_BPS {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        storage::set_auto_burn_rate(&e, burn_bps);
+    }
+}
+
+#[contractimpl]
+impl EnumerableHoldersToken for MockToken {
+    fn holders(e: Env, page: u32, page_size: u32) -> Vec<(Address, i128)> {
+        read_holder_page(&e, page, page_size)
+    }
+}
+
+#[contractimpl]
+impl TokenAnalytics for MockToken {
+    fn holder_count(e: Env) -> u64 {
+        storage::get_stats(&e).total_holders
+    }
+
+    fn transfer_count(e: Env) -> u64 {
+        storage::get_stats(&e).total_transfers
+    }
+
+    fn genesis_ledger(e: Env) -> u32 {
+        storage::get_genesis_ledger(&e)
+    }
+
+    fn holder_since(e: Env, id: Address) -> Option<u32> {
+        storage::get_holder_since(&e, &id)
+    }
+}
+
+#[contractimpl]
+impl AdminToken for MockToken {
+    fn set_name(e: Env, name: String) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if name.len() > MAX_TOKEN_NAME_LEN {
+            panic_with_error!(e, TokenError::MetadataTooLongError);
+        }
+        if !validate_string_utf8(&name) {
+            panic_with_error!(e, TokenError::InvalidMetadataError);
+        }
+        storage::extend_instance(&e);
+
+        let mut metadata = storage::get_metadata(&e);
+        let old_name = metadata.name;
+        metadata.name = name.clone();
+        storage::set_metadata(&e, &metadata);
+
+        TokenEvents::name_changed(&e, admin, old_name, name);
+    }
+
+    fn set_symbol(e: Env, symbol: String) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if symbol.len() > MAX_TOKEN_SYMBOL_LEN {
+            panic_with_error!(e, TokenError::MetadataTooLongError);
+        }
+        if !validate_string_utf8(&symbol) {
+            panic_with_error!(e, TokenError::InvalidMetadataError);
+        }
+        storage::extend_instance(&e);
+
+        let mut metadata = storage::get_metadata(&e);
+        let old_symbol = metadata.symbol;
+        metadata.symbol = symbol.clone();
+        storage::set_metadata(&e, &metadata);
+
+        TokenEvents::symbol_changed(&e, admin, old_symbol, symbol);
+    }
+}
+
+#[contractimpl]
+impl MigratableToken for MockToken {
+    fn migrate_storage(e: Env, from_version: u32) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        migrate_storage(&e, from_version);
+    }
+
+    fn is_migration_needed(e: Env) -> bool {
+        storage::get_storage_version(&e) < STORAGE_VERSION
+    }
+}
+
+#[contractimpl]
+impl ClawbackLogToken for MockToken {
+    fn clawback_log(e: Env, id: Address, page: u32, page_size: u32) -> Vec<ClawbackEntry> {
+        read_clawback_page(&e, &id, page, page_size)
+    }
+}
+
+#[contractimpl]
+impl AssetLinkedToken for MockToken {
+    fn classic_asset_id(_e: Env) -> Option<String> {
+        None
+    }
+}
+
+#[contractimpl]
+impl LargeTransferToken for MockToken {
+    fn large_transfer_threshold(e: Env) -> Option<i128> {
+        storage::get_large_transfer_threshold(&e)
+    }
+
+    fn set_large_transfer_threshold(e: Env, threshold: i128) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        if threshold <= 0 {
+            panic_with_error!(e, TokenError::OperationNotSupportedError);
+        }
+        storage::extend_instance(&e);
+
+        storage::set_large_transfer_threshold(&e, threshold);
+    }
+}
+
+#[contractimpl]
+impl MinBalanceToken for MockToken {
+    fn min_balance(e: Env, id: Address) -> i128 {
+        storage::get_min_balance(&e, &id)
+    }
+
+    fn spendable_balance(e: Env, id: Address) -> i128 {
+        read_spendable_balance(&e, &id).max(0)
+    }
+
+    fn set_min_balance(e: Env, id: Address, amount: i128) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        check_nonnegative_amount(amount);
+        storage::extend_instance(&e);
+
+        storage::set_min_balance(&e, &id, amount);
+    }
+}
+
+#[contractimpl]
+impl ExtendableAllowanceToken for MockToken {
+    fn extend_allowance(e: Env, from: Address, spender: Address, new_live_until_ledger: u32) {
+        from.require_auth();
+        storage::extend_instance(&e);
+
+        let amount = storage::get_allowance(&e, &from, &spender).amount;
+        if amount == 0 {
+            panic_with_error!(e, TokenError::AllowanceExpiredError);
+        }
+        require_permission(&e, &from, |perms| perms.can_approve);
+        if create_allowance(&e, &from, &spender, amount, new_live_until_ledger) {
+            TokenEvents::approve(&e, from, spender, amount, new_live_until_ledger);
+        }
+    }
+}
+
+#[contractimpl]
+impl SafeApproveToken for MockToken {
+    fn safe_approve(
+        e: Env,
+        from: Address,
+        spender: Address,
+        expected_amount: i128,
+        new_amount: i128,
+        live_until_ledger: u32,
+    ) {
+        from.require_auth();
+        check_nonnegative_amount(new_amount);
+        storage::extend_instance(&e);
+
+        if storage::get_allowance(&e, &from, &spender).amount != expected_amount {
+            panic_with_error!(e, TokenError::AllowanceMismatchError);
+        }
+        require_permission(&e, &from, |perms| perms.can_approve);
+        if create_allowance(&e, &from, &spender, new_amount, live_until_ledger) {
+            TokenEvents::approve(&e, from, spender, new_amount, live_until_ledger);
+        }
+    }
+}
+
+#[contractimpl]
+impl NonceToken for MockToken {
+    fn nonce(e: Env, sender: Address) -> u64 {
+        read_nonce(&e, &sender)
+    }
+}
+
+#[contractimpl]
+impl TransferLogToken for MockToken {
+    fn last_transfer(e: Env, from: Address, to: Address) -> Option<(u32, i128)> {
+        storage::get_last_transfer(&e, &from, &to)
+    }
+}
+
+#[contractimpl]
+impl AuthorizationPolicyToken for MockToken {
+    fn authorization_policy(e: Env) -> TokenAuthorizationPolicy {
+        storage::get_authorization_policy(&e)
+    }
+
+    fn set_authorization_policy(e: Env, policy: TokenAuthorizationPolicy) {
+        let admin = storage::get_admin(&e);
+        admin.require_auth();
+        storage::extend_instance(&e);
+
+        storage::set_authorization_policy(&e, policy);
+    }
+}
+
+#[contractimpl]
+impl LockedSupplyToken for MockToken {
+    fn locked_supply(e: Env) -> i128 {
+        // staked, held, and escrowed tokens are all kept in the balance of the contract
+        storage::get_balance(&e, &e.current_contract_address())
+    }
+
+    fn circulating_supply(e: Env) -> i128 {
+        storage::get_stats(&e).total_supply
+            - storage::get_balance(&e, &e.current_contract_address())
+    }
+}
+
+#[contractimpl(contracttrait)]
+impl VersionedToken for MockToken {}
@@ -3,6 +3,7 @@ use soroban_sdk::contracterror;
 /// The error codes for the contract.
 #[contracterror]
 #[derive(Copy, Clone, PartialEq, Eq)]
+#[allow(clippy::enum_variant_names)]
 pub enum TokenError {
     InternalError = 1,
     OperationNotSupportedError = 2,
@@ -14,4 +15,33 @@ pub enum TokenError {
     AllowanceError = 9,
     BalanceError = 10,
     OverflowError = 12,
+
+    BelowMinimumTransferError = 13,
+    ExceedsMaximumTransferError = 14,
+    AccountTransferLimitError = 15,
+
+    StakeNotFoundError = 16,
+    StakeLockedError = 17,
+
+    ConditionNotMetError = 18,
+
+    HoldNotFoundError = 19,
+    HoldExpiredError = 20,
+    HoldNotExpiredError = 21,
+
+    PermissionDeniedError = 22,
+
+    CapExceededError = 23,
+
+    MetadataTooLongError = 24,
+    DecimalsImmutableAfterMintError = 25,
+    InvalidMetadataError = 26,
+
+    AllowanceMismatchError = 27,
+    AllowanceExpiredError = 28,
+
+    AirdropNotFoundError = 29,
+    AirdropNotClaimableError = 30,
+
+    DecimalsTooLargeError = 31,
 }
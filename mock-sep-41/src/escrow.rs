@@ -0,0 +1,81 @@
+use crate::{
+    balance::{receive_balance, send_balance, spend_balance},
+    error::TokenError,
+    fee::{burn_on_transfer, charge_transfer_fee},
+    limits::{check_transfer_limits, use_account_transfer_limit},
+    storage::{self, AllowanceValue},
+};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+/// Set the escrowed allowance of `spender` from `from` to `amount`, moving the difference
+/// between the contract and `from`. The transfer limits of `from` are applied to any
+/// tokens added to the escrow.
+///
+/// Returns the amount moved into the escrow, or out of it if negative
+pub fn set_escrowed_allowance(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+) -> i128 {
+    if amount > 0 && expiration_ledger < e.ledger().sequence() {
+        panic_with_error!(e, TokenError::AllowanceError);
+    }
+
+    let escrow = storage::get_escrow(e, from, spender);
+    let delta = amount - escrow.amount;
+    if delta > 0 {
+        check_transfer_limits(e, delta);
+        use_account_transfer_limit(e, from, delta);
+        send_balance(e, from, delta);
+        receive_balance(e, &e.current_contract_address(), delta);
+    } else if delta < 0 {
+        spend_balance(e, &e.current_contract_address(), -delta);
+        receive_balance(e, from, -delta);
+    }
+
+    storage::set_escrow(
+        e,
+        from,
+        spender,
+        &AllowanceValue {
+            amount,
+            expiration_ledger,
+        },
+    );
+    delta
+}
+
+/// Release `amount` of the escrowed allowance of `spender` from `from` to `to`, charging
+/// any transfer fee and auto burn.
+///
+/// Returns the amount received by `to`
+pub fn spend_escrowed_allowance(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    to: &Address,
+    amount: i128,
+) -> i128 {
+    let escrow = storage::get_escrow(e, from, spender);
+    if escrow.amount < amount || e.ledger().sequence() > escrow.expiration_ledger {
+        panic_with_error!(e, TokenError::AllowanceError);
+    }
+    storage::set_escrow(
+        e,
+        from,
+        spender,
+        &AllowanceValue {
+            amount: escrow.amount - amount,
+            expiration_ledger: escrow.expiration_ledger,
+        },
+    );
+
+    spend_balance(e, &e.current_contract_address(), amount);
+    let fee = charge_transfer_fee(e, from, to, amount);
+    let burn_amount = burn_on_transfer(e, from, amount);
+    let net_amount = amount - fee - burn_amount;
+    receive_balance(e, to, net_amount);
+    net_amount
+}
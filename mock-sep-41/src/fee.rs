@@ -0,0 +1,87 @@
+use crate::{balance::receive_balance, stats::record_burn, storage};
+use sep_41_token::TokenEvents;
+use soroban_sdk::{Address, Env};
+
+/// The basis point denominator, and the maximum transfer fee
+pub(crate) const MAX_FEE_BPS: u32 = 10000;
+
+/// The maximum share of a transfer that can be burned
+pub(crate) const MAX_AUTO_BURN_BPS: u32 = 1000;
+
+/// The maximum share of a burn paid to the fee recipient
+pub(crate) const MAX_BURN_FEE_BPS: u32 = 500;
+
+/// Calculate the fee for `amount` at `fee_bps`, rounding down
+pub fn calc_fee(amount: i128, fee_bps: u32) -> i128 {
+    let bps = fee_bps as i128;
+    let denominator = MAX_FEE_BPS as i128;
+    // split the amount to avoid overflowing on large amounts
+    amount / denominator * bps + (amount % denominator) * bps / denominator
+}
+
+/// Fetch the transfer fee due on `amount` sent from `from` to `to`, and the recipient of
+/// the fee. No fee is due if either `from` or `to` is exempt.
+pub fn read_transfer_fee(
+    e: &Env,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> Option<(Address, i128)> {
+    let fee = match storage::get_transfer_fee(e) {
+        Some(fee) if fee.fee_bps > 0 => fee,
+        _ => return None,
+    };
+    if storage::is_fee_exempt(e, from) || storage::is_fee_exempt(e, to) {
+        return None;
+    }
+
+    let fee_amount = calc_fee(amount, fee.fee_bps);
+    if fee_amount > 0 {
+        Some((fee.recipient, fee_amount))
+    } else {
+        None
+    }
+}
+
+/// Charge the transfer fee on `amount` sent from `from` to `to`, and credit it to the
+/// fee recipient. No fee is charged if either `from` or `to` is exempt.
+///
+/// Returns the fee charged
+pub fn charge_transfer_fee(e: &Env, from: &Address, to: &Address, amount: i128) -> i128 {
+    match read_transfer_fee(e, from, to, amount) {
+        Some((recipient, fee_amount)) => {
+            receive_balance(e, &recipient, fee_amount);
+            TokenEvents::transfer(e, from.clone(), recipient, fee_amount);
+            fee_amount
+        }
+        None => 0,
+    }
+}
+
+/// Burn the auto burn share of `amount` sent by `from`
+///
+/// Returns the amount burned
+pub fn burn_on_transfer(e: &Env, from: &Address, amount: i128) -> i128 {
+    let burn_amount = calc_fee(amount, storage::get_auto_burn_rate(e));
+    if burn_amount > 0 {
+        record_burn(e, burn_amount);
+        TokenEvents::burn(e, from.clone(), burn_amount);
+    }
+    burn_amount
+}
+
+/// Charge the burn fee on `amount` burned by `from`, and credit it to the fee recipient.
+/// No fee is charged if no fee recipient is set.
+///
+/// Returns the fee charged
+pub fn charge_burn_fee(e: &Env, from: &Address, amount: i128) -> i128 {
+    let fee_amount = calc_fee(amount, storage::get_burn_fee_bps(e));
+    match storage::get_transfer_fee(e) {
+        Some(fee) if fee_amount > 0 => {
+            receive_balance(e, &fee.recipient, fee_amount);
+            TokenEvents::transfer(e, from.clone(), fee.recipient, fee_amount);
+            fee_amount
+        }
+        _ => 0,
+    }
+}
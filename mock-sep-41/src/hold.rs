@@ -0,0 +1,88 @@
+use crate::{
+    balance::{receive_balance, send_balance, spend_balance},
+    error::TokenError,
+    fee::{burn_on_transfer, charge_transfer_fee},
+    limits::{check_transfer_limits, use_account_transfer_limit},
+    storage::{self, Hold},
+};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+/// Lock `amount` of tokens held by `from` in the contract, until `to` accepts them or
+/// `expiry` passes. The transfer limits of `from` are applied when the hold is created.
+///
+/// Returns the id of the hold
+pub fn create_hold(e: &Env, from: &Address, to: &Address, amount: i128, expiry: u32) -> u64 {
+    if expiry < e.ledger().sequence() {
+        panic_with_error!(e, TokenError::HoldExpiredError);
+    }
+    check_transfer_limits(e, amount);
+    use_account_transfer_limit(e, from, amount);
+    send_balance(e, from, amount);
+    receive_balance(e, &e.current_contract_address(), amount);
+
+    let hold_id = storage::next_hold_id(e);
+    storage::set_hold(
+        e,
+        hold_id,
+        &Hold {
+            from: from.clone(),
+            to: to.clone(),
+            amount,
+            expiry,
+        },
+    );
+    hold_id
+}
+
+/// Fetch and remove the hold `hold_id`
+fn take_hold(e: &Env, hold_id: u64) -> Hold {
+    let hold = match storage::get_hold(e, hold_id) {
+        Some(hold) => hold,
+        None => panic_with_error!(e, TokenError::HoldNotFoundError),
+    };
+    storage::remove_hold(e, hold_id);
+    spend_balance(e, &e.current_contract_address(), hold.amount);
+    hold
+}
+
+/// Release the hold `hold_id` to its recipient, charging any transfer fee and auto burn.
+///
+/// Returns the hold and the amount received by its recipient
+pub fn accept_hold(e: &Env, hold_id: u64) -> (Hold, i128) {
+    let hold = take_hold(e, hold_id);
+    hold.to.require_auth();
+    if e.ledger().sequence() > hold.expiry {
+        panic_with_error!(e, TokenError::HoldExpiredError);
+    }
+
+    let fee = charge_transfer_fee(e, &hold.from, &hold.to, hold.amount);
+    let burn_amount = burn_on_transfer(e, &hold.from, hold.amount);
+    let net_amount = hold.amount - fee - burn_amount;
+    receive_balance(e, &hold.to, net_amount);
+    (hold, net_amount)
+}
+
+/// Return the tokens of the hold `hold_id` to its sender, at the request of its
+/// recipient.
+///
+/// Returns the rejected hold
+pub fn reject_hold(e: &Env, hold_id: u64) -> Hold {
+    let hold = take_hold(e, hold_id);
+    hold.to.require_auth();
+
+    receive_balance(e, &hold.from, hold.amount);
+    hold
+}
+
+/// Return the tokens of the hold `hold_id` to its sender, if it has expired.
+///
+/// Returns the expired hold
+pub fn expire_hold(e: &Env, hold_id: u64) -> Hold {
+    let hold = take_hold(e, hold_id);
+    if e.ledger().sequence() <= hold.expiry {
+        panic_with_error!(e, TokenError::HoldNotExpiredError);
+    }
+
+    receive_balance(e, &hold.from, hold.amount);
+    hold
+}
@@ -2,9 +2,19 @@
 
 mod allowance;
 mod balance;
+mod clawback;
 mod contract;
 mod error;
+mod escrow;
+mod fee;
+mod hold;
+mod limits;
+mod migration;
+mod permissions;
+mod snapshot;
+mod stake;
+mod stats;
 mod storage;
 mod test;
 
-pub use crate::contract::{MockToken, MockTokenClient};
+pub use crate::contract::{MockToken, MockTokenClient, MockTokenConfig};
@@ -0,0 +1,64 @@
+use crate::{
+    error::TokenError,
+    storage::{self, AccountTransferLimit},
+};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+/// Verify `amount` is within the minimum and maximum of a single transfer. Zero amounts
+/// are not subject to the limits.
+pub fn check_transfer_limits(e: &Env, amount: i128) {
+    check_minimum_transfer(e, amount);
+    if amount > storage::get_maximum_transfer(e) {
+        panic_with_error!(e, TokenError::ExceedsMaximumTransferError);
+    }
+}
+
+/// Verify `amount` is not below the minimum of a single transfer or burn. Zero amounts
+/// are not subject to the minimum.
+pub fn check_minimum_transfer(e: &Env, amount: i128) {
+    if amount != 0 && amount < storage::get_minimum_transfer(e) {
+        panic_with_error!(e, TokenError::BelowMinimumTransferError);
+    }
+}
+
+/// Verify `amount` transferred out by `from` does not exceed the transfer limit of `from`
+/// within the current window.
+///
+/// Returns the updated transfer limit of `from`, if one is set
+pub fn check_account_transfer_limit(
+    e: &Env,
+    from: &Address,
+    amount: i128,
+) -> Option<AccountTransferLimit> {
+    let mut limit = storage::get_account_transfer_limit(e, from)?;
+    let ledger = e.ledger().sequence();
+    if ledger >= limit.window_start.saturating_add(limit.window_ledgers) {
+        limit.window_start = ledger;
+        limit.volume = 0;
+    }
+    limit.volume += amount;
+    if limit.volume > limit.limit {
+        panic_with_error!(e, TokenError::AccountTransferLimitError);
+    }
+    Some(limit)
+}
+
+/// Record `amount` as transferred out by `from`, and verify it does not exceed the
+/// transfer limit of `from` within the current window.
+pub fn use_account_transfer_limit(e: &Env, from: &Address, amount: i128) {
+    if let Some(limit) = check_account_transfer_limit(e, from, amount) {
+        storage::set_account_transfer_limit(e, from, &limit);
+    }
+}
+
+/// Fetch the amount `id` has transferred out within the current window
+pub fn read_account_transfer_volume(e: &Env, id: &Address) -> i128 {
+    match storage::get_account_transfer_limit(e, id) {
+        Some(limit)
+            if e.ledger().sequence() < limit.window_start.saturating_add(limit.window_ledgers) =>
+        {
+            limit.volume
+        }
+        _ => 0,
+    }
+}
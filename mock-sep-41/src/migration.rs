@@ -0,0 +1,21 @@
+use crate::{error::TokenError, storage};
+use soroban_sdk::{panic_with_error, Env};
+
+/// The storage version written by the current contract
+pub const STORAGE_VERSION: u32 = 2;
+
+/// Migrate storage from `from_version` to `STORAGE_VERSION`, one version at a time
+pub fn migrate_storage(e: &Env, from_version: u32) {
+    if from_version != storage::get_storage_version(e) || from_version >= STORAGE_VERSION {
+        panic_with_error!(e, TokenError::OperationNotSupportedError);
+    }
+
+    for version in from_version..STORAGE_VERSION {
+        match version {
+            // version 1 has the same layout as version 2, but does not record its version
+            1 => {}
+            _ => panic_with_error!(e, TokenError::OperationNotSupportedError),
+        }
+        storage::set_storage_version(e, version + 1);
+    }
+}
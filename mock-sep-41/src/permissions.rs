@@ -0,0 +1,10 @@
+use crate::{error::TokenError, storage};
+use sep_41_token::TokenPermissions;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+/// Verify `address` is permitted to perform an operation, as determined by `permitted`
+pub fn require_permission(e: &Env, address: &Address, permitted: fn(&TokenPermissions) -> bool) {
+    if !permitted(&storage::get_permissions(e, address)) {
+        panic_with_error!(e, TokenError::PermissionDeniedError);
+    }
+}
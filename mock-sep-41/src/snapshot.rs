@@ -0,0 +1,24 @@
+use crate::storage;
+use soroban_sdk::{Address, Env};
+
+pub fn take_snapshot(e: &Env, address: &Address) {
+    let ledger = e.ledger().sequence();
+    let balance = storage::get_balance(e, address);
+    let mut snapshots = storage::get_snapshots(e, address);
+    if snapshots.ledger < ledger {
+        snapshots.prev_ledger = snapshots.ledger;
+        snapshots.prev_balance = snapshots.balance;
+    }
+    snapshots.ledger = ledger;
+    snapshots.balance = balance;
+    storage::set_snapshots(e, address, &snapshots);
+}
+
+pub fn read_snapshot(e: &Env, address: &Address) -> i128 {
+    let snapshots = storage::get_snapshots(e, address);
+    if snapshots.ledger < e.ledger().sequence() {
+        snapshots.balance
+    } else {
+        snapshots.prev_balance
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    balance::{receive_balance, send_balance, spend_balance},
+    error::TokenError,
+    storage::{self, RewardIndex, Stake, StakerRewards, DAY_IN_LEDGERS},
+};
+use sep_41_token::REWARD_RATE_SCALAR;
+use soroban_sdk::{panic_with_error, Address, Env};
+
+/// The longest lock a stake can have, of roughly 4 years
+pub(crate) const MAX_LOCK_LEDGERS: u32 = 4 * 365 * DAY_IN_LEDGERS;
+
+/// Lock `amount` of tokens held by `from` in the contract until `lock_until`.
+///
+/// Returns the id of the stake
+pub fn create_stake(e: &Env, from: &Address, amount: i128, lock_until: u32) -> u64 {
+    let ledger = e.ledger().sequence();
+    if lock_until <= ledger || lock_until - ledger > MAX_LOCK_LEDGERS {
+        panic_with_error!(e, TokenError::OperationNotSupportedError);
+    }
+
+    checkpoint_rewards(e, from);
+    send_balance(e, from, amount);
+    receive_balance(e, &e.current_contract_address(), amount);
+
+    let stake_id = storage::next_stake_id(e);
+    storage::set_stake(
+        e,
+        stake_id,
+        &Stake {
+            owner: from.clone(),
+            amount,
+            lock_until,
+        },
+    );
+    let mut stake_ids = storage::get_stake_ids(e, from);
+    stake_ids.push_back(stake_id);
+    storage::set_stake_ids(e, from, &stake_ids);
+    stake_id
+}
+
+/// Return the tokens locked by `stake_id` to its owner, if the lock has ended.
+///
+/// Returns the removed stake
+pub fn release_stake(e: &Env, stake_id: u64) -> Stake {
+    let stake = match storage::get_stake(e, stake_id) {
+        Some(stake) => stake,
+        None => panic_with_error!(e, TokenError::StakeNotFoundError),
+    };
+    stake.owner.require_auth();
+    if e.ledger().sequence() < stake.lock_until {
+        panic_with_error!(e, TokenError::StakeLockedError);
+    }
+
+    checkpoint_rewards(e, &stake.owner);
+    spend_balance(e, &e.current_contract_address(), stake.amount);
+    receive_balance(e, &stake.owner, stake.amount);
+
+    storage::remove_stake(e, stake_id);
+    let mut stake_ids = storage::get_stake_ids(e, &stake.owner);
+    if let Some(index) = stake_ids.first_index_of(stake_id) {
+        stake_ids.remove(index);
+    }
+    storage::set_stake_ids(e, &stake.owner, &stake_ids);
+    stake
+}
+
+/// Fetch the amount of tokens staked by `owner`
+pub fn read_staked_balance(e: &Env, owner: &Address) -> i128 {
+    let mut balance = 0;
+    for stake_id in storage::get_stake_ids(e, owner).iter() {
+        if let Some(stake) = storage::get_stake(e, stake_id) {
+            balance += stake.amount;
+        }
+    }
+    balance
+}
+
+/// Fetch the voting power of `owner`, decaying linearly with the remaining lock of
+/// each stake
+pub fn read_voting_power(e: &Env, owner: &Address) -> i128 {
+    let ledger = e.ledger().sequence();
+    let max = MAX_LOCK_LEDGERS as i128;
+    let mut power = 0;
+    for stake_id in storage::get_stake_ids(e, owner).iter() {
+        if let Some(stake) = storage::get_stake(e, stake_id) {
+            let remaining = stake.lock_until.saturating_sub(ledger) as i128;
+            // split the amount to avoid overflowing on large amounts
+            power += stake.amount / max * remaining + (stake.amount % max) * remaining / max;
+        }
+    }
+    power
+}
+
+/// Fetch the reward index, accumulated up to the current ledger
+fn read_reward_index(e: &Env) -> RewardIndex {
+    let mut index = storage::get_reward_index(e);
+    let ledger = e.ledger().sequence();
+    if ledger > index.last_ledger {
+        index.index = index
+            .rate
+            .checked_mul((ledger - index.last_ledger) as i128)
+            .and_then(|accrued| index.index.checked_add(accrued))
+            .unwrap_or_else(|| panic_with_error!(e, TokenError::OverflowError));
+        index.last_ledger = ledger;
+    }
+    index
+}
+
+/// Fetch the rewards of `staker`, accrued up to `index`
+fn read_staker_rewards(e: &Env, staker: &Address, index: &RewardIndex) -> StakerRewards {
+    let mut rewards = storage::get_staker_rewards(e, staker);
+    let staked = read_staked_balance(e, staker);
+    rewards.accrued = staked
+        .checked_mul(index.index - rewards.index)
+        .and_then(|accrued| rewards.accrued.checked_add(accrued / REWARD_RATE_SCALAR))
+        .unwrap_or_else(|| panic_with_error!(e, TokenError::OverflowError));
+    rewards.index = index.index;
+    rewards
+}
+
+/// Record the rewards accrued by `staker` up to the current ledger. Must be called
+/// before the staked balance of `staker` changes.
+fn checkpoint_rewards(e: &Env, staker: &Address) -> StakerRewards {
+    let rewards = read_staker_rewards(e, staker, &read_reward_index(e));
+    storage::set_staker_rewards(e, staker, &rewards);
+    rewards
+}
+
+/// Set the reward rate from the current ledger onwards
+pub fn set_reward_rate(e: &Env, rate: i128) {
+    let mut index = read_reward_index(e);
+    index.rate = rate;
+    storage::set_reward_index(e, &index);
+}
+
+/// Fetch the rewards accrued by `staker` that have not been harvested
+pub fn read_pending_rewards(e: &Env, staker: &Address) -> i128 {
+    read_staker_rewards(e, staker, &read_reward_index(e)).accrued
+}
+
+/// Pay out the pending rewards of `staker` from the reward pool.
+///
+/// Returns the amount of rewards paid out
+pub fn harvest_rewards(e: &Env, staker: &Address) -> i128 {
+    let mut rewards = checkpoint_rewards(e, staker);
+    let pool = storage::get_reward_pool(e);
+    let amount = rewards.accrued.min(pool);
+    if amount > 0 {
+        rewards.accrued -= amount;
+        storage::set_staker_rewards(e, staker, &rewards);
+        storage::set_reward_pool(e, pool - amount);
+        receive_balance(e, staker, amount);
+    }
+    amount
+}
@@ -0,0 +1,69 @@
+use crate::{error::TokenError, storage};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+/// Record `amount` of tokens as minted, and verify the total supply does not exceed
+/// the cap
+pub fn record_mint(e: &Env, amount: i128) {
+    let mut stats = storage::get_stats(e);
+    stats.total_supply = match stats.total_supply.checked_add(amount) {
+        Some(total_supply) if total_supply <= storage::get_cap(e) => total_supply,
+        _ => panic_with_error!(e, TokenError::CapExceededError),
+    };
+    stats.total_minted += amount;
+    storage::set_stats(e, &stats);
+}
+
+/// Record `amount` of tokens as burned
+pub fn record_burn(e: &Env, amount: i128) {
+    let mut stats = storage::get_stats(e);
+    stats.total_supply -= amount;
+    stats.total_burned += amount;
+    storage::set_stats(e, &stats);
+}
+
+/// Record a transfer
+pub fn record_transfer(e: &Env) {
+    let mut stats = storage::get_stats(e);
+    stats.total_transfers += 1;
+    storage::set_stats(e, &stats);
+}
+
+/// Record the balance of `address` changing from `prev_balance` to `balance`, updating
+/// the holders if the address gained or lost its last token, and the ledger the address
+/// first held a token in
+pub fn record_balance_change(e: &Env, address: &Address, prev_balance: i128, balance: i128) {
+    let mut stats = storage::get_stats(e);
+    if prev_balance == 0 && balance > 0 {
+        storage::set_holder(e, stats.total_holders, address);
+        stats.total_holders += 1;
+        if storage::get_holder_since(e, address).is_none() {
+            storage::set_holder_since(e, address, e.ledger().sequence());
+        }
+    } else if prev_balance > 0 && balance == 0 {
+        // move the last holder into the index of the removed holder
+        stats.total_holders -= 1;
+        let index = storage::get_holder_index(e, address);
+        let last = storage::get_holder(e, stats.total_holders);
+        storage::remove_holder(e, stats.total_holders, address);
+        if last != *address {
+            storage::set_holder(e, index, &last);
+        }
+    } else {
+        return;
+    }
+    storage::set_stats(e, &stats);
+}
+
+/// Fetch the page of size `page_size` at index `page` of the holders and their balances
+pub fn read_holder_page(e: &Env, page: u32, page_size: u32) -> Vec<(Address, i128)> {
+    let holder_count = storage::get_stats(e).total_holders;
+    let start = (page as u64).saturating_mul(page_size as u64);
+    let end = start.saturating_add(page_size as u64).min(holder_count);
+    let mut holders = Vec::new(e);
+    for index in start..end {
+        let holder = storage::get_holder(e, index);
+        let balance = storage::get_balance(e, &holder);
+        holders.push_back((holder, balance));
+    }
+    holders
+}
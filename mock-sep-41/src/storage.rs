@@ -1,6 +1,7 @@
+use sep_41_token::{ClawbackEntry, TokenAuthorizationPolicy, TokenPermissions, TokenStats};
 use soroban_sdk::{
-    contracttype, symbol_short, unwrap::UnwrapOptimized, Address, Env, IntoVal, String, Symbol,
-    TryFromVal, Val,
+    contracttype, symbol_short, unwrap::UnwrapOptimized, vec, Address, Env, IntoVal, String,
+    Symbol, TryFromVal, Val, Vec,
 };
 
 pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
@@ -10,8 +11,31 @@ pub(crate) const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_I
 pub(crate) const BALANCE_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
 pub(crate) const BALANCE_LIFETIME_THRESHOLD: u32 = BALANCE_BUMP_AMOUNT - 20 * DAY_IN_LEDGERS;
 
-const METADATA_KEY: Symbol = symbol_short!("METADATA");
+/// The number of ledgers between the airdrop claims of a recipient
+pub(crate) const AIRDROP_PERIOD_LEDGERS: u32 = DAY_IN_LEDGERS;
+
+/// The number of ledgers the most recent transfer between two addresses is kept for
+pub(crate) const LAST_TRANSFER_LIFETIME: u32 = 120;
+
+pub(crate) const METADATA_KEY: Symbol = symbol_short!("METADATA");
 const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
+const FEE_KEY: Symbol = symbol_short!("FEE");
+const BURN_FEE_KEY: Symbol = symbol_short!("BURN_FEE");
+const MIN_XFER_KEY: Symbol = symbol_short!("MIN_XFER");
+const MAX_XFER_KEY: Symbol = symbol_short!("MAX_XFER");
+const LARGE_XFER_KEY: Symbol = symbol_short!("LRG_XFER");
+const REPLACEMENT_KEY: Symbol = symbol_short!("REPLACE");
+const STAKE_ID_KEY: Symbol = symbol_short!("STAKE_ID");
+const REWARDS_KEY: Symbol = symbol_short!("REWARDS");
+const REWARD_POOL_KEY: Symbol = symbol_short!("RWD_POOL");
+const STATS_KEY: Symbol = symbol_short!("STATS");
+const HOLD_ID_KEY: Symbol = symbol_short!("HOLD_ID");
+const CAP_KEY: Symbol = symbol_short!("CAP");
+const BURN_RATE_KEY: Symbol = symbol_short!("BURN_BPS");
+const VERSION_KEY: Symbol = symbol_short!("VERSION");
+const APPROVALS_KEY: Symbol = symbol_short!("APPROVALS");
+const GENESIS_KEY: Symbol = symbol_short!("GENESIS");
+const POLICY_KEY: Symbol = symbol_short!("POLICY");
 
 #[derive(Clone)]
 #[contracttype]
@@ -21,6 +45,22 @@ pub struct TokenMetadata {
     pub symbol: String,
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct TransferFee {
+    pub fee_bps: u32,
+    pub recipient: Address,
+}
+
+/// The outbound transfer limit of an address, and its volume in the current window
+#[contracttype]
+pub struct AccountTransferLimit {
+    pub limit: i128,
+    pub window_ledgers: u32,
+    pub window_start: u32,
+    pub volume: i128,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct AllowanceDataKey {
@@ -28,12 +68,69 @@ pub struct AllowanceDataKey {
     pub spender: Address,
 }
 
+#[derive(Clone)]
+#[contracttype]
+pub struct TransferDataKey {
+    pub from: Address,
+    pub to: Address,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct RecipientAllowanceDataKey {
+    pub from: Address,
+    pub spender: Address,
+    pub to: Address,
+}
+
 #[contracttype]
 pub struct AllowanceValue {
     pub amount: i128,
     pub expiration_ledger: u32,
 }
 
+/// The two most recent balance snapshots of an address, such that a snapshot older than
+/// the current ledger is always available
+#[contracttype]
+pub struct BalanceSnapshots {
+    pub ledger: u32,
+    pub balance: i128,
+    pub prev_ledger: u32,
+    pub prev_balance: i128,
+}
+
+/// Tokens locked by `owner` until the ledger `lock_until`
+#[contracttype]
+pub struct Stake {
+    pub owner: Address,
+    pub amount: i128,
+    pub lock_until: u32,
+}
+
+/// The reward rate, and the cumulative rewards per staked token as of `last_ledger`
+#[contracttype]
+pub struct RewardIndex {
+    pub rate: i128,
+    pub index: i128,
+    pub last_ledger: u32,
+}
+
+/// The rewards accrued by a staker as of the reward index `index`
+#[contracttype]
+pub struct StakerRewards {
+    pub index: i128,
+    pub accrued: i128,
+}
+
+/// Tokens sent by `from` held by the contract until `to` accepts them, or `expiry` passes
+#[contracttype]
+pub struct Hold {
+    pub from: Address,
+    pub to: Address,
+    pub amount: i128,
+    pub expiry: u32,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -41,6 +138,26 @@ pub enum DataKey {
     Balance(Address),
     Nonce(Address),
     State(Address),
+    Spenders(Address),
+    Snapshot(Address),
+    FeeExempt(Address),
+    TransferLimit(Address),
+    Allocation(Address),
+    Stake(u64),
+    Stakes(Address),
+    Rewards(Address),
+    Hold(u64),
+    Permissions(Address),
+    Holder(u64),
+    HolderIndex(Address),
+    RecipientAllowance(RecipientAllowanceDataKey),
+    Escrow(AllowanceDataKey),
+    ClawbackLog(Address),
+    LastTransfer(TransferDataKey),
+    MinBalance(Address),
+    HolderSince(Address),
+    Airdrop(Address),
+    AirdropClaim(Address),
 }
 
 /// Bump the instance lifetime by the defined amount
@@ -70,6 +187,9 @@ fn get_persistent_default<K: IntoVal<Env, Val>, V: TryFromVal<Env, Val>>(
 
 //********** Instance **********//
 
+// Token-wide configuration, such as the metadata. Instance storage shares the TTL of the
+// contract, which is bumped by `extend_instance` on each invocation.
+
 // Admin
 
 pub fn get_admin(e: &Env) -> Address {
@@ -94,8 +214,186 @@ pub fn set_metadata(e: &Env, metadata: &TokenMetadata) {
     e.storage().instance().set(&METADATA_KEY, metadata);
 }
 
+// Transfer Fee
+
+pub fn get_transfer_fee(e: &Env) -> Option<TransferFee> {
+    e.storage().instance().get(&FEE_KEY)
+}
+
+pub fn set_transfer_fee(e: &Env, fee: &TransferFee) {
+    e.storage().instance().set(&FEE_KEY, fee);
+}
+
+// Burn Fee
+
+pub fn get_burn_fee_bps(e: &Env) -> u32 {
+    e.storage().instance().get(&BURN_FEE_KEY).unwrap_or(0)
+}
+
+pub fn set_burn_fee_bps(e: &Env, fee_bps: u32) {
+    e.storage().instance().set(&BURN_FEE_KEY, &fee_bps);
+}
+
+// Auto Burn
+
+pub fn get_auto_burn_rate(e: &Env) -> u32 {
+    e.storage().instance().get(&BURN_RATE_KEY).unwrap_or(0)
+}
+
+pub fn set_auto_burn_rate(e: &Env, burn_bps: u32) {
+    e.storage().instance().set(&BURN_RATE_KEY, &burn_bps);
+}
+
+// Transfer Limits
+
+pub fn get_minimum_transfer(e: &Env) -> i128 {
+    e.storage().instance().get(&MIN_XFER_KEY).unwrap_or(1)
+}
+
+pub fn set_minimum_transfer(e: &Env, min: i128) {
+    e.storage().instance().set(&MIN_XFER_KEY, &min);
+}
+
+pub fn get_maximum_transfer(e: &Env) -> i128 {
+    e.storage()
+        .instance()
+        .get(&MAX_XFER_KEY)
+        .unwrap_or(i128::MAX)
+}
+
+pub fn set_maximum_transfer(e: &Env, max: i128) {
+    e.storage().instance().set(&MAX_XFER_KEY, &max);
+}
+
+pub fn get_large_transfer_threshold(e: &Env) -> Option<i128> {
+    e.storage().instance().get(&LARGE_XFER_KEY)
+}
+
+pub fn set_large_transfer_threshold(e: &Env, threshold: i128) {
+    e.storage().instance().set(&LARGE_XFER_KEY, &threshold);
+}
+
+// Replacement Token
+
+pub fn get_replacement_token(e: &Env) -> Option<Address> {
+    e.storage().instance().get(&REPLACEMENT_KEY)
+}
+
+pub fn set_replacement_token(e: &Env, new_token: &Address) {
+    e.storage().instance().set(&REPLACEMENT_KEY, new_token);
+}
+
+// Stake Ids
+
+/// Fetch the next stake id, and increment it
+pub fn next_stake_id(e: &Env) -> u64 {
+    let id: u64 = e.storage().instance().get(&STAKE_ID_KEY).unwrap_or(0);
+    e.storage().instance().set(&STAKE_ID_KEY, &(id + 1));
+    id
+}
+
+// Hold Ids
+
+/// Fetch the next hold id, and increment it
+pub fn next_hold_id(e: &Env) -> u64 {
+    let id: u64 = e.storage().instance().get(&HOLD_ID_KEY).unwrap_or(0);
+    e.storage().instance().set(&HOLD_ID_KEY, &(id + 1));
+    id
+}
+
+// Rewards
+
+pub fn get_reward_index(e: &Env) -> RewardIndex {
+    e.storage()
+        .instance()
+        .get(&REWARDS_KEY)
+        .unwrap_or(RewardIndex {
+            rate: 0,
+            index: 0,
+            last_ledger: e.ledger().sequence(),
+        })
+}
+
+pub fn set_reward_index(e: &Env, index: &RewardIndex) {
+    e.storage().instance().set(&REWARDS_KEY, index);
+}
+
+pub fn get_reward_pool(e: &Env) -> i128 {
+    e.storage().instance().get(&REWARD_POOL_KEY).unwrap_or(0)
+}
+
+pub fn set_reward_pool(e: &Env, amount: i128) {
+    e.storage().instance().set(&REWARD_POOL_KEY, &amount);
+}
+
+// Storage Version
+
+/// Fetch the storage version. Storage written before the version was recorded is version 1.
+pub fn get_storage_version(e: &Env) -> u32 {
+    e.storage().instance().get(&VERSION_KEY).unwrap_or(1)
+}
+
+pub fn set_storage_version(e: &Env, version: u32) {
+    e.storage().instance().set(&VERSION_KEY, &version);
+}
+
+// Supply Cap
+
+pub fn get_cap(e: &Env) -> i128 {
+    e.storage().instance().get(&CAP_KEY).unwrap_or(i128::MAX)
+}
+
+pub fn set_cap(e: &Env, cap: i128) {
+    e.storage().instance().set(&CAP_KEY, &cap);
+}
+
+// Genesis
+
+/// Fetch the ledger the token was initialized in. Tokens initialized before the ledger
+/// was recorded return 0.
+pub fn get_genesis_ledger(e: &Env) -> u32 {
+    e.storage().instance().get(&GENESIS_KEY).unwrap_or(0)
+}
+
+pub fn set_genesis_ledger(e: &Env, ledger: u32) {
+    e.storage().instance().set(&GENESIS_KEY, &ledger);
+}
+
+// Approvals
+
+/// Fetch the number of spenders approved across all addresses
+pub fn get_approval_count(e: &Env) -> u64 {
+    e.storage().instance().get(&APPROVALS_KEY).unwrap_or(0)
+}
+
+pub fn set_approval_count(e: &Env, count: u64) {
+    e.storage().instance().set(&APPROVALS_KEY, &count);
+}
+
+// Stats
+
+pub fn get_stats(e: &Env) -> TokenStats {
+    e.storage()
+        .instance()
+        .get(&STATS_KEY)
+        .unwrap_or(TokenStats {
+            total_supply: 0,
+            total_minted: 0,
+            total_burned: 0,
+            total_transfers: 0,
+            total_holders: 0,
+        })
+}
+
+pub fn set_stats(e: &Env, stats: &TokenStats) {
+    e.storage().instance().set(&STATS_KEY, stats);
+}
+
 //********** Persistent **********//
 
+// Per-address state that must not be lost, such as balances. Each entry has its own TTL,
+// which is bumped whenever the entry is read or written.
+
 // Balance
 
 pub fn get_balance(e: &Env, address: &Address) -> i128 {
@@ -112,22 +410,458 @@ pub fn set_balance(e: &Env, address: &Address, balance: &i128) {
     e.storage()
         .persistent()
         .set(&DataKey::Balance(address.clone()), balance);
+    bump_balance_ttl(e, address);
+}
+
+/// Extend the TTL of the balance of `address`, if it has one
+pub fn bump_balance_ttl(e: &Env, address: &Address) {
+    let key = DataKey::Balance(address.clone());
+    if e.storage().persistent().has(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+}
+
+// Authorization
+
+pub fn get_authorized(e: &Env, address: &Address) -> bool {
+    get_persistent_default(
+        e,
+        &DataKey::State(address.clone()),
+        true,
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+/// Fetch the authorization status explicitly set for `address`, if any
+pub fn get_explicit_authorization(e: &Env, address: &Address) -> Option<bool> {
+    let key = DataKey::State(address.clone());
+    let authorized = e.storage().persistent().get(&key);
+    if authorized.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    authorized
+}
+
+/// Set the authorization status of `address`. Authorizations are only stored when the
+/// policy requires them, as addresses are otherwise authorized by default.
+pub fn set_authorized(e: &Env, address: &Address, authorize: bool) {
+    let key = DataKey::State(address.clone());
+    if authorize && get_authorization_policy(e) != TokenAuthorizationPolicy::AuthorizationRequired {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, &authorize);
+    }
+}
+
+pub fn get_authorization_policy(e: &Env) -> TokenAuthorizationPolicy {
+    e.storage()
+        .instance()
+        .get(&POLICY_KEY)
+        .unwrap_or(TokenAuthorizationPolicy::FreezeOnly)
+}
+
+pub fn set_authorization_policy(e: &Env, policy: TokenAuthorizationPolicy) {
+    e.storage().instance().set(&POLICY_KEY, &policy);
+}
+
+// Permissions
+
+pub fn get_permissions(e: &Env, address: &Address) -> TokenPermissions {
+    get_persistent_default(
+        e,
+        &DataKey::Permissions(address.clone()),
+        TokenPermissions {
+            can_send: true,
+            can_receive: true,
+            can_burn: true,
+            can_approve: true,
+        },
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+pub fn set_permissions(e: &Env, address: &Address, perms: &TokenPermissions) {
+    let key = DataKey::Permissions(address.clone());
+    if perms.can_send && perms.can_receive && perms.can_burn && perms.can_approve {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, perms);
+    }
+}
+
+// Holders
+
+/// Fetch the holder at `index`
+pub fn get_holder(e: &Env, index: u64) -> Address {
+    let key = DataKey::Holder(index);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    e.storage().persistent().get(&key).unwrap_optimized()
+}
+
+/// Fetch the index of the holder `address`
+pub fn get_holder_index(e: &Env, address: &Address) -> u64 {
+    let key = DataKey::HolderIndex(address.clone());
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    e.storage().persistent().get(&key).unwrap_optimized()
+}
+
+/// Store `address` as the holder at `index`
+pub fn set_holder(e: &Env, index: u64, address: &Address) {
+    e.storage()
+        .persistent()
+        .set(&DataKey::Holder(index), address);
+    e.storage()
+        .persistent()
+        .set(&DataKey::HolderIndex(address.clone()), &index);
+}
+
+/// Fetch the ledger `address` first received a non-zero balance in
+pub fn get_holder_since(e: &Env, address: &Address) -> Option<u32> {
+    let key = DataKey::HolderSince(address.clone());
+    let result = e.storage().persistent().get(&key);
+    if result.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    result
+}
+
+pub fn set_holder_since(e: &Env, address: &Address, ledger: u32) {
+    e.storage()
+        .persistent()
+        .set(&DataKey::HolderSince(address.clone()), &ledger);
+}
+
+/// Remove the holder entry at `index`, and the index of `address`
+pub fn remove_holder(e: &Env, index: u64, address: &Address) {
+    e.storage().persistent().remove(&DataKey::Holder(index));
+    e.storage()
+        .persistent()
+        .remove(&DataKey::HolderIndex(address.clone()));
+}
+
+// Spenders
+
+/// Fetch the spenders that have been approved by `from`
+pub fn get_spenders(e: &Env, from: &Address) -> Vec<Address> {
+    get_persistent_default(
+        e,
+        &DataKey::Spenders(from.clone()),
+        vec![e],
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+pub fn set_spenders(e: &Env, from: &Address, spenders: &Vec<Address>) {
+    let key = DataKey::Spenders(from.clone());
+    if spenders.is_empty() {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, spenders);
+    }
+}
+
+// Snapshots
+
+pub fn get_snapshots(e: &Env, address: &Address) -> BalanceSnapshots {
+    get_persistent_default(
+        e,
+        &DataKey::Snapshot(address.clone()),
+        BalanceSnapshots {
+            ledger: 0,
+            balance: 0,
+            prev_ledger: 0,
+            prev_balance: 0,
+        },
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+pub fn set_snapshots(e: &Env, address: &Address, snapshots: &BalanceSnapshots) {
+    e.storage()
+        .persistent()
+        .set(&DataKey::Snapshot(address.clone()), snapshots);
+}
+
+// Fee Exemptions
+
+pub fn is_fee_exempt(e: &Env, address: &Address) -> bool {
+    get_persistent_default(
+        e,
+        &DataKey::FeeExempt(address.clone()),
+        false,
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+pub fn set_fee_exempt(e: &Env, address: &Address, exempt: bool) {
+    let key = DataKey::FeeExempt(address.clone());
+    if exempt {
+        e.storage().persistent().set(&key, &true);
+    } else {
+        e.storage().persistent().remove(&key);
+    }
+}
+
+// Account Transfer Limits
+
+pub fn get_account_transfer_limit(e: &Env, address: &Address) -> Option<AccountTransferLimit> {
+    let key = DataKey::TransferLimit(address.clone());
+    let result = e.storage().persistent().get(&key);
+    if result.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    result
+}
+
+pub fn set_account_transfer_limit(e: &Env, address: &Address, limit: &AccountTransferLimit) {
+    e.storage()
+        .persistent()
+        .set(&DataKey::TransferLimit(address.clone()), limit);
+}
+
+// Allocations
+
+pub fn get_allocation(e: &Env, address: &Address) -> i128 {
+    get_persistent_default(
+        e,
+        &DataKey::Allocation(address.clone()),
+        0_i128,
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+pub fn set_allocation(e: &Env, address: &Address, amount: i128) {
+    let key = DataKey::Allocation(address.clone());
+    if amount == 0 {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, &amount);
+    }
+}
+
+// Minimum Balances
+
+pub fn get_min_balance(e: &Env, address: &Address) -> i128 {
+    get_persistent_default(
+        e,
+        &DataKey::MinBalance(address.clone()),
+        0_i128,
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+pub fn set_min_balance(e: &Env, address: &Address, amount: i128) {
+    let key = DataKey::MinBalance(address.clone());
+    if amount == 0 {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, &amount);
+    }
+}
+
+// Airdrops
+
+pub fn get_airdrop_amount(e: &Env, address: &Address) -> Option<i128> {
+    let key = DataKey::Airdrop(address.clone());
+    let result = e.storage().persistent().get(&key);
+    if result.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    result
+}
+
+pub fn set_airdrop_amount(e: &Env, address: &Address, amount: i128) {
+    e.storage()
+        .persistent()
+        .set(&DataKey::Airdrop(address.clone()), &amount);
+}
+
+pub fn remove_airdrop_amount(e: &Env, address: &Address) {
+    e.storage()
+        .persistent()
+        .remove(&DataKey::Airdrop(address.clone()));
+}
+
+/// Fetch the ledger `address` last claimed an airdrop in, if within the current period
+pub fn get_last_airdrop_claim(e: &Env, address: &Address) -> Option<u32> {
+    e.storage()
+        .temporary()
+        .get(&DataKey::AirdropClaim(address.clone()))
+}
+
+/// Record an airdrop claim by `address` in the current ledger, kept until the next period
+pub fn set_last_airdrop_claim(e: &Env, address: &Address) {
+    let key = DataKey::AirdropClaim(address.clone());
+    e.storage().temporary().set(&key, &e.ledger().sequence());
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, AIRDROP_PERIOD_LEDGERS, AIRDROP_PERIOD_LEDGERS);
+}
+
+// Stakes
+
+pub fn get_stake(e: &Env, stake_id: u64) -> Option<Stake> {
+    let key = DataKey::Stake(stake_id);
+    let result = e.storage().persistent().get(&key);
+    if result.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    result
+}
+
+pub fn set_stake(e: &Env, stake_id: u64, stake: &Stake) {
+    e.storage()
+        .persistent()
+        .set(&DataKey::Stake(stake_id), stake);
+}
+
+pub fn remove_stake(e: &Env, stake_id: u64) {
+    e.storage().persistent().remove(&DataKey::Stake(stake_id));
+}
+
+/// Fetch the ids of the stakes owned by `owner`
+pub fn get_stake_ids(e: &Env, owner: &Address) -> Vec<u64> {
+    get_persistent_default(
+        e,
+        &DataKey::Stakes(owner.clone()),
+        vec![e],
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+pub fn set_stake_ids(e: &Env, owner: &Address, stake_ids: &Vec<u64>) {
+    let key = DataKey::Stakes(owner.clone());
+    if stake_ids.is_empty() {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, stake_ids);
+    }
+}
+
+// Staker Rewards
+
+pub fn get_staker_rewards(e: &Env, staker: &Address) -> StakerRewards {
+    get_persistent_default(
+        e,
+        &DataKey::Rewards(staker.clone()),
+        StakerRewards {
+            index: 0,
+            accrued: 0,
+        },
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+pub fn set_staker_rewards(e: &Env, staker: &Address, rewards: &StakerRewards) {
+    e.storage()
+        .persistent()
+        .set(&DataKey::Rewards(staker.clone()), rewards);
+}
+
+// Holds
+
+pub fn get_hold(e: &Env, hold_id: u64) -> Option<Hold> {
+    let key = DataKey::Hold(hold_id);
+    let result = e.storage().persistent().get(&key);
+    if result.is_some() {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    }
+    result
+}
+
+pub fn set_hold(e: &Env, hold_id: u64, hold: &Hold) {
+    e.storage().persistent().set(&DataKey::Hold(hold_id), hold);
+}
+
+pub fn remove_hold(e: &Env, hold_id: u64) {
+    e.storage().persistent().remove(&DataKey::Hold(hold_id));
+}
+
+// Escrow
+
+/// Fetch the escrowed allowance of `spender` from `from`
+pub fn get_escrow(e: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+    get_persistent_default(
+        e,
+        &DataKey::Escrow(AllowanceDataKey {
+            from: from.clone(),
+            spender: spender.clone(),
+        }),
+        AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+        BALANCE_LIFETIME_THRESHOLD,
+        BALANCE_BUMP_AMOUNT,
+    )
+}
+
+/// Store the escrowed allowance of `spender` from `from`, removing it if it is empty
+pub fn set_escrow(e: &Env, from: &Address, spender: &Address, escrow: &AllowanceValue) {
+    let key = DataKey::Escrow(AllowanceDataKey {
+        from: from.clone(),
+        spender: spender.clone(),
+    });
+    if escrow.amount == 0 {
+        e.storage().persistent().remove(&key);
+    } else {
+        e.storage().persistent().set(&key, escrow);
+    }
 }
 
 //********** Temporary **********//
 
+// State that is worthless after it expires, such as allowances. Each entry lives until its
+// expiration ledger, and is then deleted rather than archived, so an expired allowance
+// never needs to be restored.
+
 // Allowance
 
+/// Fetch the allowance of `spender` from `from`. An expired allowance has an amount of 0.
 pub fn get_allowance(e: &Env, from: &Address, spender: &Address) -> AllowanceValue {
     let key = DataKey::Allowance(AllowanceDataKey {
         from: from.clone(),
         spender: spender.clone(),
     });
-    let temp = e.storage().temporary().get(&key);
-    temp.unwrap_or_else(|| AllowanceValue {
-        amount: 0,
-        expiration_ledger: 0,
-    })
+    let temp: Option<AllowanceValue> = e.storage().temporary().get(&key);
+    match temp {
+        Some(allowance) if allowance.expiration_ledger < e.ledger().sequence() => AllowanceValue {
+            amount: 0,
+            expiration_ledger: allowance.expiration_ledger,
+        },
+        Some(allowance) => allowance,
+        None => AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+    }
 }
 
 pub fn set_allowance(
@@ -146,12 +880,147 @@ pub fn set_allowance(
         expiration_ledger,
     };
     e.storage().temporary().set(&key, &allowance);
+    bump_allowance_ttl(e, from, spender);
+}
+
+/// Delete the allowance of `spender` from `from`, rather than storing a zero allowance
+pub fn remove_allowance(e: &Env, from: &Address, spender: &Address) {
+    let key = DataKey::Allowance(AllowanceDataKey {
+        from: from.clone(),
+        spender: spender.clone(),
+    });
+    e.storage().temporary().remove(&key);
+}
+
+/// Delete the allowance of `spender` from `from`, and remove `spender` from the spenders
+/// of `from`
+///
+/// Returns true if `spender` was removed from the spenders
+pub fn clean_zero_allowance(e: &Env, from: &Address, spender: &Address) -> bool {
+    remove_allowance(e, from, spender);
+    let mut spenders = get_spenders(e, from);
+    match spenders.first_index_of(spender) {
+        Some(index) => {
+            spenders.remove(index);
+            set_spenders(e, from, &spenders);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Extend the TTL of the allowance of `spender` from `from` to its expiration ledger,
+/// such that it does not expire before it can be used
+pub fn bump_allowance_ttl(e: &Env, from: &Address, spender: &Address) {
+    let key = DataKey::Allowance(AllowanceDataKey {
+        from: from.clone(),
+        spender: spender.clone(),
+    });
+    if let Some(allowance) = e.storage().temporary().get::<_, AllowanceValue>(&key) {
+        if allowance.amount > 0 {
+            if let Some(ledgers_to_live) = allowance
+                .expiration_ledger
+                .checked_sub(e.ledger().sequence())
+            {
+                e.storage()
+                    .temporary()
+                    .extend_ttl(&key, ledgers_to_live, ledgers_to_live);
+            }
+        }
+    }
+}
+
+/// Fetch the allowance of `spender` from `from` to `to`. An expired allowance has an
+/// amount of 0.
+pub fn get_recipient_allowance(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    to: &Address,
+) -> AllowanceValue {
+    let key = DataKey::RecipientAllowance(RecipientAllowanceDataKey {
+        from: from.clone(),
+        spender: spender.clone(),
+        to: to.clone(),
+    });
+    let temp: Option<AllowanceValue> = e.storage().temporary().get(&key);
+    match temp {
+        Some(allowance) if allowance.expiration_ledger < e.ledger().sequence() => AllowanceValue {
+            amount: 0,
+            expiration_ledger: allowance.expiration_ledger,
+        },
+        Some(allowance) => allowance,
+        None => AllowanceValue {
+            amount: 0,
+            expiration_ledger: 0,
+        },
+    }
+}
+
+pub fn set_recipient_allowance(
+    e: &Env,
+    from: &Address,
+    spender: &Address,
+    to: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+) {
+    let key = DataKey::RecipientAllowance(RecipientAllowanceDataKey {
+        from: from.clone(),
+        spender: spender.clone(),
+        to: to.clone(),
+    });
+    let allowance = AllowanceValue {
+        amount,
+        expiration_ledger,
+    };
+    e.storage().temporary().set(&key, &allowance);
     if amount > 0 {
-        let ledgers_to_live = expiration_ledger
-            .checked_sub(e.ledger().sequence())
-            .unwrap_optimized();
-        e.storage()
-            .temporary()
-            .extend_ttl(&key, ledgers_to_live, ledgers_to_live);
+        if let Some(ledgers_to_live) = expiration_ledger.checked_sub(e.ledger().sequence()) {
+            e.storage()
+                .temporary()
+                .extend_ttl(&key, ledgers_to_live, ledgers_to_live);
+        }
     }
 }
+
+// Clawback Log
+
+pub fn get_clawback_log(e: &Env, address: &Address) -> Vec<ClawbackEntry> {
+    e.storage()
+        .temporary()
+        .get(&DataKey::ClawbackLog(address.clone()))
+        .unwrap_or(vec![e])
+}
+
+pub fn set_clawback_log(e: &Env, address: &Address, log: &Vec<ClawbackEntry>) {
+    let key = DataKey::ClawbackLog(address.clone());
+    e.storage().temporary().set(&key, log);
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+// Last Transfers
+
+pub fn get_last_transfer(e: &Env, from: &Address, to: &Address) -> Option<(u32, i128)> {
+    e.storage()
+        .temporary()
+        .get(&DataKey::LastTransfer(TransferDataKey {
+            from: from.clone(),
+            to: to.clone(),
+        }))
+}
+
+pub fn set_last_transfer(e: &Env, from: &Address, to: &Address, amount: i128) {
+    let key = DataKey::LastTransfer(TransferDataKey {
+        from: from.clone(),
+        to: to.clone(),
+    });
+    e.storage()
+        .temporary()
+        .set(&key, &(e.ledger().sequence(), amount));
+    e.storage()
+        .temporary()
+        .extend_ttl(&key, LAST_TRANSFER_LIFETIME, LAST_TRANSFER_LIFETIME);
+}
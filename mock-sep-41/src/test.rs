@@ -3,13 +3,22 @@ extern crate std;
 
 use crate::{
     contract::MockToken,
-    storage::{AllowanceDataKey, DataKey},
+    error::TokenError,
+    storage::{self, AllowanceDataKey, DataKey, BALANCE_BUMP_AMOUNT, DAY_IN_LEDGERS},
     MockTokenClient,
 };
+use sep_41_token::{
+    oracle::OracleCondition, receiver::TokenReceiver, validate, AllowanceInfo, ClawbackEntry,
+    TokenAuthorizationPolicy, TokenPermissions, TokenStats, TransferReceipt,
+    DEFAULT_CLAWBACK_LOG_SIZE, SEP41_VERSION,
+};
 use soroban_sdk::{
-    symbol_short,
-    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation},
-    Address, Env, IntoVal, Symbol,
+    contract, contractimpl, symbol_short,
+    testutils::{
+        storage::{Persistent as _, Temporary as _},
+        Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger,
+    },
+    vec, Address, Bytes, Env, IntoVal, String, Symbol,
 };
 
 fn create_token<'a>(e: &Env, admin: &Address) -> MockTokenClient<'a> {
@@ -245,6 +254,15 @@ fn initialize_already_initialized() {
     token.initialize(&admin, &10, &"name".into_val(&e), &"symbol".into_val(&e));
 }
 
+#[test]
+#[should_panic(expected = "Error(Contract, #31)")]
+fn decimal_is_over_max_decimals() {
+    let e = Env::default();
+    let admin = Address::generate(&e);
+    let token = MockTokenClient::new(&e, &e.register(MockToken {}, ()));
+    token.initialize(&admin, &39, &"name".into_val(&e), &"symbol".into_val(&e));
+}
+
 #[test]
 #[should_panic(expected = "Error(Contract, #2)")]
 fn decimal_is_over_max() {
@@ -259,6 +277,27 @@ fn decimal_is_over_max() {
     );
 }
 
+#[test]
+fn test_allowance_live_until_current_ledger() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    token.mint(&user, &1000);
+
+    // an allowance that lives until the current ledger can be spent within that ledger
+    token.approve(&user, &spender, &500, &100);
+    token.transfer_from(&spender, &user, &spender, &200);
+    assert_eq!(token.allowance(&user, &spender), 300);
+
+    e.ledger().set_sequence_number(101);
+    assert_eq!(token.allowance(&user, &spender), 0);
+}
+
 #[test]
 fn test_zero_allowance() {
     // Here we test that transfer_from with a 0 amount does not create an empty allowance
@@ -279,3 +318,3517 @@ fn test_zero_allowance() {
         assert!(!e.storage().temporary().has(&key));
     });
 }
+
+#[test]
+fn test_transfer_from_zero_amount() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let from = Address::generate(&e);
+    let to = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    token.mint(&from, &1000);
+    token.approve(&from, &spender, &500, &200);
+
+    // a zero amount transfer moves nothing, but is still authorized by the spender
+    token.transfer_from(&spender, &from, &to, &0);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            spender.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "transfer_from"),
+                    (&spender, &from, &to, 0_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), &from, &to).into_val(&e),
+                0_i128.into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(token.allowance(&from, &spender), 500);
+    assert_eq!(token.balance(&from), 1000);
+    assert_eq!(token.balance(&to), 0);
+}
+
+#[test]
+fn test_revoke_all_allowances() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let spender1 = Address::generate(&e);
+    let spender2 = Address::generate(&e);
+    let spender3 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.approve(&user1, &spender1, &500, &200);
+    token.approve(&user1, &spender2, &300, &200);
+    token.approve(&user1, &spender3, &100, &200);
+    token.approve(&user1, &spender3, &0, &200);
+
+    token.revoke_all_allowances(&user1);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "revoke_all_allowances"),
+                    (&user1,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("approve"), user1.clone(), spender1.clone()).into_val(&e),
+                (0_i128, 0_u32).into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (symbol_short!("approve"), user1.clone(), spender2.clone()).into_val(&e),
+                (0_i128, 0_u32).into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.allowance(&user1, &spender1), 0);
+    assert_eq!(token.allowance(&user1, &spender2), 0);
+    assert_eq!(token.allowance(&user1, &spender3), 0);
+    e.as_contract(&token.address, || {
+        let key = DataKey::Spenders(user1.clone());
+        assert!(!e.storage().persistent().has(&key));
+    });
+}
+
+#[test]
+fn test_balance_snapshot() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    assert_eq!(token.snapshot_balance(&user1), 0);
+
+    // snapshots taken during the current ledger are ignored
+    token.take_balance_snapshot(&user1);
+    assert_eq!(token.snapshot_balance(&user1), 0);
+
+    e.ledger().set_sequence_number(101);
+    assert_eq!(token.snapshot_balance(&user1), 1000);
+
+    token.transfer(&user1, &user2, &400);
+    token.take_balance_snapshot(&user1);
+    assert_eq!(token.snapshot_balance(&user1), 1000);
+
+    e.ledger().set_sequence_number(102);
+    assert_eq!(token.snapshot_balance(&user1), 600);
+    assert_eq!(token.snapshot_balance(&user2), 0);
+}
+
+#[test]
+fn test_transfer_fee() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let user3 = Address::generate(&e);
+    let fee_recipient = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    assert_eq!(token.transfer_fee_bps(), 0);
+    assert_eq!(token.fee_recipient(), None);
+
+    token.set_transfer_fee(&1000, &fee_recipient);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_transfer_fee"),
+                    (1000_u32, &fee_recipient).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.transfer_fee_bps(), 1000);
+    assert_eq!(token.fee_recipient(), Some(fee_recipient.clone()));
+
+    token.transfer(&user1, &user2, &1000);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    symbol_short!("transfer"),
+                    user1.clone(),
+                    fee_recipient.clone()
+                )
+                    .into_val(&e),
+                100_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), user1.clone(), user2.clone()).into_val(&e),
+                900_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 9000);
+    assert_eq!(token.balance(&user2), 900);
+    assert_eq!(token.balance(&fee_recipient), 100);
+
+    token.approve(&user1, &user3, &1000, &200);
+    token.transfer_from(&user3, &user1, &user2, &1000);
+    assert_eq!(token.allowance(&user1, &user3), 0);
+    assert_eq!(token.balance(&user1), 8000);
+    assert_eq!(token.balance(&user2), 1800);
+    assert_eq!(token.balance(&fee_recipient), 200);
+}
+
+#[test]
+fn test_transfer_from_fee_spends_gross_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let fee_recipient = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    token.set_transfer_fee(&1000, &fee_recipient);
+    token.approve(&user1, &spender, &1000, &200);
+
+    // the allowance is spent by the amount sent, not the amount received after the fee
+    token.transfer_from(&spender, &user1, &user2, &100);
+    assert_eq!(token.allowance(&user1, &spender), 900);
+    assert_eq!(token.balance(&user1), 9900);
+    assert_eq!(token.balance(&user2), 90);
+    assert_eq!(token.balance(&fee_recipient), 10);
+}
+
+#[test]
+fn test_fee_exempt() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let fee_recipient = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    token.set_transfer_fee(&1000, &fee_recipient);
+
+    token.add_fee_exempt(&user2);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "add_fee_exempt"),
+                    (&user2,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    Symbol::new(&e, "fee_exempt_added"),
+                    admin.clone(),
+                    user2.clone()
+                )
+                    .into_val(&e),
+                ().into_val(&e)
+            ),
+        ]
+    );
+    assert!(token.is_fee_exempt(&user2));
+
+    // exempt as the recipient
+    token.transfer(&user1, &user2, &1000);
+    assert_eq!(token.balance(&user2), 1000);
+    // exempt as the sender
+    token.transfer(&user2, &user1, &500);
+    assert_eq!(token.balance(&user1), 9500);
+    assert_eq!(token.balance(&fee_recipient), 0);
+
+    token.remove_fee_exempt(&user2);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    Symbol::new(&e, "fee_exempt_removed"),
+                    admin.clone(),
+                    user2.clone()
+                )
+                    .into_val(&e),
+                ().into_val(&e)
+            ),
+        ]
+    );
+    assert!(!token.is_fee_exempt(&user2));
+
+    token.transfer(&user1, &user2, &1000);
+    assert_eq!(token.balance(&user2), 1400);
+    assert_eq!(token.balance(&fee_recipient), 100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn transfer_fee_over_max() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let fee_recipient = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.set_transfer_fee(&10001, &fee_recipient);
+}
+
+#[test]
+fn test_set_authorized() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    assert!(token.authorized(&user1));
+
+    token.set_authorized(&user1, &false);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_authorized"),
+                    (&user1, false).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    Symbol::new(&e, "set_authorized"),
+                    admin.clone(),
+                    user1.clone()
+                )
+                    .into_val(&e),
+                false.into_val(&e)
+            ),
+        ]
+    );
+    assert!(!token.authorized(&user1));
+    assert!(token.try_transfer(&user1, &user2, &100).is_err());
+    assert!(token.try_transfer(&user2, &user1, &0).is_err());
+
+    token.set_authorized(&user1, &true);
+    assert!(token.authorized(&user1));
+    token.transfer(&user1, &user2, &100);
+    assert_eq!(token.balance(&user2), 100);
+}
+
+#[test]
+fn test_bulk_set_authorized() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.set_authorized(&user2, &false);
+
+    let updates = vec![&e, (user1.clone(), false), (user2.clone(), true)];
+    token.bulk_set_authorized(&updates);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "bulk_set_authorized"),
+                    (updates.clone(),).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    Symbol::new(&e, "set_authorized"),
+                    admin.clone(),
+                    user1.clone()
+                )
+                    .into_val(&e),
+                false.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (
+                    Symbol::new(&e, "set_authorized"),
+                    admin.clone(),
+                    user2.clone()
+                )
+                    .into_val(&e),
+                true.into_val(&e)
+            ),
+        ]
+    );
+    assert!(!token.authorized(&user1));
+    assert!(token.authorized(&user2));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn bulk_set_authorized_includes_admin() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.bulk_set_authorized(&vec![&e, (user1, false), (admin, false)]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn bulk_set_authorized_includes_zero_address() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    let zero = Address::from_str(&e, validate::ZERO_ACCOUNT_ADDRESS);
+    token.bulk_set_authorized(&vec![&e, (user1, false), (zero, false)]);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn bulk_set_authorized_over_max() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    let mut updates = vec![&e];
+    for _ in 0..101 {
+        updates.push_back((Address::generate(&e), false));
+    }
+    token.bulk_set_authorized(&updates);
+}
+
+#[test]
+fn test_transfer_many_to_one() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let receiver = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.mint(&user2, &1000);
+    token.approve(&user1, &spender, &500, &200);
+    token.approve(&user2, &spender, &500, &200);
+
+    let froms = vec![&e, (user1.clone(), 300_i128), (user2.clone(), 200_i128)];
+    token.transfer_many_to_one(&spender, &froms, &receiver);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            spender.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "transfer_many_to_one"),
+                    (&spender, froms.clone(), &receiver).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), user1.clone(), receiver.clone()).into_val(&e),
+                300_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), user2.clone(), receiver.clone()).into_val(&e),
+                200_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&receiver), 500);
+    assert_eq!(token.balance(&user1), 700);
+    assert_eq!(token.balance(&user2), 800);
+    assert_eq!(token.allowance(&user1, &spender), 200);
+    assert_eq!(token.allowance(&user2, &spender), 300);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")]
+fn transfer_many_to_one_insufficient_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let receiver = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.mint(&user2, &1000);
+    token.approve(&user1, &spender, &500, &200);
+
+    let froms = vec![&e, (user1, 300_i128), (user2, 200_i128)];
+    token.transfer_many_to_one(&spender, &froms, &receiver);
+}
+
+#[test]
+fn test_minimum_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    assert_eq!(token.minimum_transfer(), 1);
+
+    token.set_minimum_transfer(&100);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_minimum_transfer"),
+                    (100_i128,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.minimum_transfer(), 100);
+
+    token.transfer(&user1, &user2, &100);
+    token.approve(&user1, &user2, &1000, &200);
+    token.transfer_from(&user2, &user1, &user2, &100);
+    token.burn(&user1, &100);
+    token.burn_from(&user2, &user1, &100);
+    assert_eq!(token.balance(&user1), 600);
+    assert_eq!(token.balance(&user2), 200);
+
+    // zero amounts are not subject to the minimum
+    token.transfer(&user1, &user2, &0);
+
+    assert_eq!(
+        token.try_transfer(&user1, &user2, &99),
+        Err(Ok(TokenError::BelowMinimumTransferError.into()))
+    );
+    assert_eq!(
+        token.try_transfer_from(&user2, &user1, &user2, &99),
+        Err(Ok(TokenError::BelowMinimumTransferError.into()))
+    );
+    assert_eq!(
+        token.try_burn(&user1, &99),
+        Err(Ok(TokenError::BelowMinimumTransferError.into()))
+    );
+    assert_eq!(
+        token.try_burn_from(&user2, &user1, &99),
+        Err(Ok(TokenError::BelowMinimumTransferError.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn minimum_transfer_not_positive() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.set_minimum_transfer(&0);
+}
+
+#[test]
+fn test_maximum_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    assert_eq!(token.maximum_transfer(), i128::MAX);
+
+    token.set_maximum_transfer(&100);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_maximum_transfer"),
+                    (100_i128,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.maximum_transfer(), 100);
+
+    token.transfer(&user1, &user2, &100);
+    token.approve(&user1, &user2, &1000, &200);
+    token.transfer_from(&user2, &user1, &user2, &100);
+    assert_eq!(token.balance(&user2), 200);
+
+    assert_eq!(
+        token.try_transfer(&user1, &user2, &101),
+        Err(Ok(TokenError::ExceedsMaximumTransferError.into()))
+    );
+    assert_eq!(
+        token.try_transfer_from(&user2, &user1, &user2, &101),
+        Err(Ok(TokenError::ExceedsMaximumTransferError.into()))
+    );
+
+    // burns are not subject to the maximum
+    token.burn(&user1, &101);
+    token.burn_from(&user2, &user1, &101);
+    assert_eq!(token.balance(&user1), 598);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn maximum_transfer_below_minimum() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.set_minimum_transfer(&100);
+    token.set_maximum_transfer(&99);
+}
+
+#[test]
+fn test_account_transfer_limit() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.set_account_transfer_limit(&user1, &300, &10);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_account_transfer_limit"),
+                    (&user1, 300_i128, 10_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.account_transfer_volume(&user1), 0);
+
+    token.transfer(&user1, &user2, &200);
+    token.approve(&user1, &user2, &1000, &200);
+    token.transfer_from(&user2, &user1, &user2, &100);
+    assert_eq!(token.account_transfer_volume(&user1), 300);
+    assert_eq!(
+        token.try_transfer(&user1, &user2, &1),
+        Err(Ok(TokenError::AccountTransferLimitError.into()))
+    );
+
+    // other accounts are not limited
+    token.transfer(&user2, &user1, &300);
+    assert_eq!(token.account_transfer_volume(&user2), 0);
+
+    // the window resets
+    e.ledger().set_sequence_number(110);
+    assert_eq!(token.account_transfer_volume(&user1), 0);
+    token.transfer(&user1, &user2, &300);
+    assert_eq!(token.account_transfer_volume(&user1), 300);
+    assert_eq!(token.balance(&user1), 700);
+}
+
+#[test]
+fn test_claim_mint() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.allocate(&user1, &600);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("allocate"),
+                    (&user1, 600_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    token.allocate(&user1, &400);
+    assert_eq!(token.claimable(&user1), 1000);
+    assert_eq!(token.balance(&user1), 0);
+
+    token.claim(&user1);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("claim"),
+                    (&user1,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("mint"), admin.clone(), user1.clone()).into_val(&e),
+                1000_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.claimable(&user1), 0);
+    assert_eq!(token.balance(&user1), 1000);
+
+    // claiming again is a no-op
+    token.claim(&user1);
+    assert_eq!(e.events().all().len(), 0);
+    assert_eq!(token.balance(&user1), 1000);
+}
+
+#[test]
+fn test_migrate() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    let new_token = create_token(&e, &token.address);
+
+    token.mint(&user1, &1000);
+    assert_eq!(token.replacement_token(), None);
+
+    token.set_replacement_token(&new_token.address);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_replacement_token"),
+                    (&new_token.address,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.replacement_token(), Some(new_token.address.clone()));
+
+    token.migrate(&user1, &400);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("migrate"),
+                    (&user1, 400_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                new_token.address.clone(),
+                (symbol_short!("mint"), token.address.clone(), user1.clone()).into_val(&e),
+                400_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (symbol_short!("burn"), user1.clone()).into_val(&e),
+                400_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (
+                    symbol_short!("migrate"),
+                    user1.clone(),
+                    new_token.address.clone()
+                )
+                    .into_val(&e),
+                400_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 600);
+    assert_eq!(new_token.balance(&user1), 400);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn migrate_without_replacement() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.migrate(&user1, &400);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #13)")]
+fn migrate_below_minimum_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    let new_token = create_token(&e, &token.address);
+
+    token.mint(&user1, &1000);
+    token.set_replacement_token(&new_token.address);
+    token.set_minimum_transfer(&100);
+    token.migrate(&user1, &1);
+}
+
+#[test]
+fn test_clawback() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.set_authorized(&user1, &false);
+
+    token.clawback(&user1, &400);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("clawback"),
+                    (&user1, 400_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("clawback"), admin.clone(), user1.clone()).into_val(&e),
+                400_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 600);
+}
+
+#[test]
+fn test_batch_burn() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.mint(&user2, &1000);
+
+    let burns = vec![&e, (user1.clone(), 400_i128), (user2.clone(), 1000_i128)];
+    token.batch_burn(&burns);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "batch_burn"),
+                    (burns.clone(),).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("clawback"), admin.clone(), user1.clone()).into_val(&e),
+                400_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (symbol_short!("clawback"), admin.clone(), user2.clone()).into_val(&e),
+                1000_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 600);
+    assert_eq!(token.balance(&user2), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn batch_burn_insufficient_balance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.mint(&user2, &1000);
+
+    token.batch_burn(&vec![&e, (user1, 400_i128), (user2, 1001_i128)]);
+}
+
+#[test]
+fn test_simulate_balance_after_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let fee_recipient = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    token.mint(&user2, &500);
+    assert_eq!(
+        token.simulate_balance_after_transfer(&user1, &user2, &1000),
+        (9000, 1500)
+    );
+    assert_eq!(
+        token.simulate_balance_after_transfer(&user1, &user1, &1000),
+        (10000, 10000)
+    );
+
+    token.set_transfer_fee(&1000, &fee_recipient);
+    assert_eq!(
+        token.simulate_balance_after_transfer(&user1, &user2, &1000),
+        (9000, 1400)
+    );
+
+    // no state is changed by the simulation
+    assert_eq!(token.balance(&user1), 10000);
+    assert_eq!(token.balance(&user2), 500);
+    assert_eq!(token.balance(&fee_recipient), 0);
+
+    token.transfer(&user1, &user2, &1000);
+    assert_eq!(token.balance(&user1), 9000);
+    assert_eq!(token.balance(&user2), 1400);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn simulate_balance_after_transfer_insufficient_balance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.simulate_balance_after_transfer(&user1, &user2, &1001);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #4)")]
+fn simulate_balance_after_transfer_unauthorized() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.set_authorized(&user2, &false);
+    token.simulate_balance_after_transfer(&user1, &user2, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn simulate_balance_after_transfer_cannot_send() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.set_permissions(
+        &user1,
+        &TokenPermissions {
+            can_send: false,
+            can_receive: true,
+            can_burn: true,
+            can_approve: true,
+        },
+    );
+    token.simulate_balance_after_transfer(&user1, &user2, &100);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn simulate_balance_after_transfer_cannot_receive() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.set_permissions(
+        &user2,
+        &TokenPermissions {
+            can_send: true,
+            can_receive: false,
+            can_burn: true,
+            can_approve: true,
+        },
+    );
+    token.simulate_balance_after_transfer(&user1, &user2, &100);
+}
+
+#[contract]
+struct Receiver;
+
+#[contractimpl]
+impl TokenReceiver for Receiver {
+    fn on_receive(e: Env, from: Address, amount: i128, data: Bytes) {
+        if data.is_empty() {
+            panic!("rejected");
+        }
+        e.storage()
+            .instance()
+            .set(&symbol_short!("received"), &(from, amount, data));
+    }
+}
+
+#[test]
+fn test_transfer_and_notify() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let receiver = e.register(Receiver {}, ());
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    let data = Bytes::from_array(&e, &[1, 2, 3]);
+
+    token.transfer_and_notify(&user1, &receiver, &400, &data);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "transfer_and_notify"),
+                    (&user1, &receiver, 400_i128, data.clone()).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), user1.clone(), receiver.clone()).into_val(&e),
+                400_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 600);
+    assert_eq!(token.balance(&receiver), 400);
+    let received: (Address, i128, Bytes) = e.as_contract(&receiver, || {
+        e.storage()
+            .instance()
+            .get(&symbol_short!("received"))
+            .unwrap()
+    });
+    assert_eq!(received, (user1.clone(), 400, data.clone()));
+
+    // accounts are not notified
+    token.transfer_and_notify(&user1, &user2, &100, &data);
+    assert_eq!(token.balance(&user1), 500);
+    assert_eq!(token.balance(&user2), 100);
+
+    // transfer is reverted if the receiver rejects it
+    let result = token.try_transfer_and_notify(&user1, &receiver, &100, &Bytes::new(&e));
+    assert!(result.is_err());
+    assert_eq!(token.balance(&user1), 500);
+    assert_eq!(token.balance(&receiver), 400);
+}
+
+#[test]
+fn test_approval_count() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let spender1 = Address::generate(&e);
+    let spender2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    assert_eq!(token.approval_count(&user1), 0);
+    assert_eq!(token.active_approvals(), 0);
+
+    token.approve(&user1, &spender1, &100, &200);
+    token.approve(&user1, &spender2, &200, &300);
+    token.approve(&user2, &spender1, &300, &1000);
+    token.approve(&user1, &spender1, &150, &200);
+    assert_eq!(token.approval_count(&user1), 2);
+    assert_eq!(token.approval_count(&user2), 1);
+    assert_eq!(token.active_approvals(), 3);
+
+    // expired allowances are not counted by address, but remain counted in total
+    e.ledger().set_sequence_number(250);
+    assert_eq!(token.approval_count(&user1), 1);
+    assert_eq!(token.active_approvals(), 3);
+
+    token.approve(&user1, &spender2, &0, &0);
+    assert_eq!(token.approval_count(&user1), 0);
+    assert_eq!(token.active_approvals(), 2);
+
+    token.revoke_all_allowances(&user1);
+    assert_eq!(token.active_approvals(), 1);
+    token.revoke_all_allowances(&user2);
+    assert_eq!(token.approval_count(&user2), 0);
+    assert_eq!(token.active_approvals(), 0);
+
+    // an allowance spent in full is no longer counted
+    token.mint(&user1, &1000);
+    token.approve(&user1, &spender1, &300, &1000);
+    token.transfer_from(&spender1, &user1, &spender2, &100);
+    assert_eq!(token.active_approvals(), 1);
+    token.transfer_from(&spender1, &user1, &spender2, &200);
+    assert_eq!(token.approval_count(&user1), 0);
+    assert_eq!(token.active_approvals(), 0);
+    assert_eq!(token.allowance_pages(&user1, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_allowance_pages() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let spender1 = Address::generate(&e);
+    let spender2 = Address::generate(&e);
+    let spender3 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    assert_eq!(token.allowance_pages(&user1, &0, &2), vec![&e]);
+
+    token.approve(&user1, &spender1, &100, &200);
+    token.approve(&user1, &spender2, &200, &300);
+    token.approve(&user1, &spender3, &300, &400);
+    token.approve(&user1, &spender1, &150, &200);
+
+    assert_eq!(
+        token.allowance_pages(&user1, &0, &2),
+        vec![
+            &e,
+            (
+                spender1.clone(),
+                AllowanceInfo {
+                    amount: 150,
+                    expiration_ledger: 200
+                }
+            ),
+            (
+                spender2.clone(),
+                AllowanceInfo {
+                    amount: 200,
+                    expiration_ledger: 300
+                }
+            ),
+        ]
+    );
+    assert_eq!(
+        token.allowance_pages(&user1, &1, &2),
+        vec![
+            &e,
+            (
+                spender3.clone(),
+                AllowanceInfo {
+                    amount: 300,
+                    expiration_ledger: 400
+                }
+            ),
+        ]
+    );
+    assert_eq!(token.allowance_pages(&user1, &2, &2), vec![&e]);
+    assert_eq!(
+        token.allowance_pages(&user1, &u32::MAX, &u32::MAX),
+        vec![&e]
+    );
+
+    // revoked allowances are removed
+    token.approve(&user1, &spender2, &0, &0);
+    assert_eq!(
+        token.allowance_pages(&user1, &0, &10),
+        vec![
+            &e,
+            (
+                spender1.clone(),
+                AllowanceInfo {
+                    amount: 150,
+                    expiration_ledger: 200
+                }
+            ),
+            (
+                spender3.clone(),
+                AllowanceInfo {
+                    amount: 300,
+                    expiration_ledger: 400
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_stake() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    let max_lock = 4 * 365 * 17280;
+
+    token.mint(&user1, &10000);
+
+    let stake_id = token.stake(&user1, &4000, &(100 + max_lock));
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("stake"),
+                    (&user1, 4000_i128, 100_u32 + max_lock).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    symbol_short!("transfer"),
+                    user1.clone(),
+                    token.address.clone()
+                )
+                    .into_val(&e),
+                4000_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(stake_id, 0);
+    assert_eq!(token.balance(&user1), 6000);
+    assert_eq!(token.balance(&token.address), 4000);
+    assert_eq!(token.staked_balance(&user1), 4000);
+    assert_eq!(token.voting_power(&user1), 4000);
+
+    let stake_id_2 = token.stake(&user1, &2000, &(100 + max_lock / 4));
+    assert_eq!(stake_id_2, 1);
+    assert_eq!(token.staked_balance(&user1), 6000);
+    assert_eq!(token.voting_power(&user1), 4500);
+
+    // voting power decays with the remaining lock
+    e.ledger().set_sequence_number(100 + max_lock / 4);
+    assert_eq!(token.voting_power(&user1), 3000);
+
+    token.unstake(&stake_id_2);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("unstake"),
+                    (stake_id_2,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    symbol_short!("transfer"),
+                    token.address.clone(),
+                    user1.clone()
+                )
+                    .into_val(&e),
+                2000_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 6000);
+    assert_eq!(token.balance(&token.address), 4000);
+    assert_eq!(token.staked_balance(&user1), 4000);
+    assert_eq!(token.voting_power(&user1), 3000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #17)")]
+fn unstake_before_lock_until() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    let stake_id = token.stake(&user1, &4000, &200);
+
+    e.ledger().set_sequence_number(199);
+    token.unstake(&stake_id);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn stake_over_max_lock() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    token.stake(&user1, &4000, &(101 + 4 * 365 * 17280));
+}
+
+#[test]
+fn test_staking_rewards() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    token.mint(&user2, &10000);
+
+    // 0.01 tokens per ledger per staked token
+    token.set_reward_rate(&100000);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_reward_rate"),
+                    (100000_i128,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    token.set_reward_pool(&1500);
+    assert_eq!(token.reward_pool(), 1500);
+
+    let stake_id = token.stake(&user1, &1000, &1000);
+    token.stake(&user2, &2000, &1000);
+    assert_eq!(token.pending_rewards(&user1), 0);
+
+    e.ledger().set_sequence_number(150);
+    assert_eq!(token.pending_rewards(&user1), 500);
+    assert_eq!(token.pending_rewards(&user2), 1000);
+
+    token.harvest_rewards(&user1);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "harvest_rewards"),
+                    (&user1,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("mint"), admin.clone(), user1.clone()).into_val(&e),
+                500_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 9500);
+    assert_eq!(token.pending_rewards(&user1), 0);
+    assert_eq!(token.reward_pool(), 1000);
+
+    // rewards stop accruing when the rate is changed
+    e.ledger().set_sequence_number(200);
+    token.set_reward_rate(&0);
+    e.ledger().set_sequence_number(1000);
+    assert_eq!(token.pending_rewards(&user1), 500);
+    assert_eq!(token.pending_rewards(&user2), 2000);
+
+    // rewards are kept when unstaking
+    token.unstake(&stake_id);
+    assert_eq!(token.pending_rewards(&user1), 500);
+
+    // rewards in excess of the pool remain pending
+    token.harvest_rewards(&user2);
+    assert_eq!(token.balance(&user2), 9000);
+    assert_eq!(token.pending_rewards(&user2), 1000);
+    assert_eq!(token.reward_pool(), 0);
+
+    token.harvest_rewards(&user1);
+    assert_eq!(e.events().all(), vec![&e]);
+    assert_eq!(token.balance(&user1), 10500);
+    assert_eq!(token.pending_rewards(&user1), 500);
+}
+
+#[contract]
+struct Condition;
+
+#[contractimpl]
+impl OracleCondition for Condition {
+    fn check_condition(_e: Env, condition_data: Bytes) -> bool {
+        condition_data.first() == Some(1)
+    }
+}
+
+#[test]
+fn test_conditional_burn() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let oracle = e.register(Condition {}, ());
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    let condition = Bytes::from_array(&e, &[1]);
+
+    token.conditional_burn(&user1, &400, &oracle, &condition);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "conditional_burn"),
+                    (&user1, 400_i128, &oracle, condition.clone()).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("burn"), user1.clone()).into_val(&e),
+                400_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 600);
+}
+
+#[test]
+fn conditional_burn_condition_not_met() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let oracle = e.register(Condition {}, ());
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+
+    let result = token.try_conditional_burn(&user1, &400, &oracle, &Bytes::from_array(&e, &[0]));
+    assert_eq!(result, Err(Ok(TokenError::ConditionNotMetError.into())));
+    assert_eq!(token.balance(&user1), 1000);
+}
+
+#[test]
+fn test_stats() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    assert_eq!(
+        token.stats(),
+        TokenStats {
+            total_supply: 0,
+            total_minted: 0,
+            total_burned: 0,
+            total_transfers: 0,
+            total_holders: 0,
+        }
+    );
+
+    token.mint(&user1, &1000);
+    token.transfer(&user1, &user2, &400);
+    token.transfer(&user1, &user2, &100);
+    token.burn(&user2, &200);
+    assert_eq!(
+        token.stats(),
+        TokenStats {
+            total_supply: 800,
+            total_minted: 1000,
+            total_burned: 200,
+            total_transfers: 2,
+            total_holders: 2,
+        }
+    );
+
+    // holders are removed once their balance hits zero
+    token.transfer(&user1, &user2, &500);
+    token.clawback(&user2, &100);
+    assert_eq!(
+        token.stats(),
+        TokenStats {
+            total_supply: 700,
+            total_minted: 1000,
+            total_burned: 300,
+            total_transfers: 3,
+            total_holders: 1,
+        }
+    );
+    assert_eq!(token.holder_count(), 1);
+    assert_eq!(token.transfer_count(), 3);
+}
+
+#[test]
+fn test_hold_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+
+    let hold_id = token.hold_transfer(&user1, &user2, &400, &200);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "hold_transfer"),
+                    (&user1, &user2, 400_i128, 200_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    symbol_short!("transfer"),
+                    user1.clone(),
+                    token.address.clone()
+                )
+                    .into_val(&e),
+                400_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 600);
+    assert_eq!(token.balance(&token.address), 400);
+
+    token.accept_hold(&hold_id);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user2.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "accept_hold"),
+                    (hold_id,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    symbol_short!("transfer"),
+                    token.address.clone(),
+                    user2.clone()
+                )
+                    .into_val(&e),
+                400_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user2), 400);
+    assert_eq!(token.balance(&token.address), 0);
+
+    // a hold can only be settled once
+    let result = token.try_reject_hold(&hold_id);
+    assert_eq!(result, Err(Ok(TokenError::HoldNotFoundError.into())));
+
+    let hold_id = token.hold_transfer(&user1, &user2, &100, &200);
+    token.reject_hold(&hold_id);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user2.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "reject_hold"),
+                    (hold_id,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.balance(&user1), 600);
+    assert_eq!(token.balance(&user2), 400);
+}
+
+#[test]
+fn test_expire_hold() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    let hold_id = token.hold_transfer(&user1, &user2, &400, &200);
+
+    let result = token.try_expire_hold(&hold_id);
+    assert_eq!(result, Err(Ok(TokenError::HoldNotExpiredError.into())));
+
+    e.ledger().set_sequence_number(201);
+    let result = token.try_accept_hold(&hold_id);
+    assert_eq!(result, Err(Ok(TokenError::HoldExpiredError.into())));
+
+    token.expire_hold(&hold_id);
+    assert_eq!(e.auths(), std::vec![]);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    symbol_short!("transfer"),
+                    token.address.clone(),
+                    user1.clone()
+                )
+                    .into_val(&e),
+                400_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 1000);
+    assert_eq!(token.balance(&token.address), 0);
+}
+
+#[test]
+fn test_permissions() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    let all = TokenPermissions {
+        can_send: true,
+        can_receive: true,
+        can_burn: true,
+        can_approve: true,
+    };
+    assert_eq!(token.permissions(&user1), all);
+
+    let receive_only = TokenPermissions {
+        can_send: false,
+        can_receive: true,
+        can_burn: false,
+        can_approve: false,
+    };
+    token.set_permissions(&user1, &receive_only);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_permissions"),
+                    (&user1, receive_only.clone()).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.permissions(&user1), receive_only);
+
+    token.mint(&user1, &1000);
+    token.mint(&user2, &1000);
+    token.transfer(&user2, &user1, &100);
+    assert_eq!(token.balance(&user1), 1100);
+
+    let denied = Err(Ok(TokenError::PermissionDeniedError.into()));
+    assert_eq!(token.try_transfer(&user1, &user2, &100), denied);
+    assert_eq!(token.try_burn(&user1, &100), denied);
+    assert_eq!(token.try_approve(&user1, &user2, &100, &100), denied);
+
+    token.set_permissions(
+        &user2,
+        &TokenPermissions {
+            can_receive: false,
+            ..all.clone()
+        },
+    );
+    token.set_permissions(&user1, &all);
+    assert_eq!(token.try_transfer(&user1, &user2, &100), denied);
+    assert_eq!(token.try_mint(&user2, &100), denied);
+
+    token.transfer(&user2, &user1, &100);
+    assert_eq!(token.balance(&user1), 1200);
+    assert_eq!(token.balance(&user2), 800);
+}
+
+#[test]
+fn test_transfer_detailed() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let fee_recipient = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    token.mint(&user2, &500);
+    token.set_transfer_fee(&1000, &fee_recipient);
+
+    let receipt = token.transfer_detailed(&user1, &user2, &1000);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "transfer_detailed"),
+                    (&user1, &user2, 1000_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    symbol_short!("transfer"),
+                    user1.clone(),
+                    fee_recipient.clone()
+                )
+                    .into_val(&e),
+                100_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), user1.clone(), user2.clone()).into_val(&e),
+                900_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(
+        receipt,
+        TransferReceipt {
+            gross_amount: 1000,
+            net_amount: 900,
+            fee: 100,
+            from_balance_after: 9000,
+            to_balance_after: 1400,
+        }
+    );
+}
+
+#[test]
+fn test_mint_to_many() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    let recipients = vec![&e, (user1.clone(), 400_i128), (user2.clone(), 600_i128)];
+    token.mint_to_many(&admin, &recipients);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "mint_to_many"),
+                    (&admin, recipients.clone()).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("mint"), admin.clone(), user1.clone()).into_val(&e),
+                400_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (symbol_short!("mint"), admin.clone(), user2.clone()).into_val(&e),
+                600_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 400);
+    assert_eq!(token.balance(&user2), 600);
+    assert_eq!(token.total_supply(), 1000);
+
+    let not_admin = Address::generate(&e);
+    let result = token.try_mint_to_many(&not_admin, &recipients);
+    assert_eq!(result, Err(Ok(TokenError::UnauthorizedError.into())));
+}
+
+#[test]
+fn test_supply_cap() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    assert_eq!(token.cap(), i128::MAX);
+    token.mint(&user1, &500);
+
+    token.set_cap(&1000);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("set_cap"),
+                    (1000_i128,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.cap(), 1000);
+
+    let result = token.try_set_cap(&499);
+    assert_eq!(
+        result,
+        Err(Ok(TokenError::OperationNotSupportedError.into()))
+    );
+
+    // the whole batch is reverted if the sum exceeds the cap
+    let result = token.try_mint_to_many(
+        &admin,
+        &vec![&e, (user1.clone(), 250), (user2.clone(), 251)],
+    );
+    assert_eq!(result, Err(Ok(TokenError::CapExceededError.into())));
+    assert_eq!(token.balance(&user1), 500);
+    assert_eq!(token.total_supply(), 500);
+
+    token.mint_to_many(
+        &admin,
+        &vec![&e, (user1.clone(), 250), (user2.clone(), 250)],
+    );
+    assert_eq!(token.total_supply(), 1000);
+
+    // burned tokens can be minted again
+    token.burn(&user2, &100);
+    token.mint(&user2, &100);
+    let result = token.try_mint(&user2, &1);
+    assert_eq!(result, Err(Ok(TokenError::CapExceededError.into())));
+}
+
+#[test]
+fn test_total_burned() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    assert_eq!(token.total_burned(), 0);
+
+    token.burn(&user1, &100);
+    assert_eq!(token.total_burned(), 100);
+
+    token.approve(&user1, &spender, &200, &200);
+    token.burn_from(&spender, &user1, &200);
+    assert_eq!(token.total_burned(), 300);
+
+    token.set_auto_burn_rate(&100);
+    token.transfer(&user1, &user2, &1000);
+    assert_eq!(token.total_burned(), 310);
+
+    token.clawback(&user2, &90);
+    assert_eq!(token.total_burned(), 400);
+    assert_eq!(token.total_supply(), 10000 - 400);
+}
+
+#[test]
+fn test_remaining_mintable() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    assert!(!token.max_supply_reached());
+    assert_eq!(token.remaining_mintable(), i128::MAX);
+
+    token.set_cap(&1000);
+    token.mint(&user1, &999);
+    assert!(!token.max_supply_reached());
+    assert_eq!(token.remaining_mintable(), 1);
+
+    token.mint(&user1, &1);
+    assert!(token.max_supply_reached());
+    assert_eq!(token.remaining_mintable(), 0);
+
+    // a supply above the cap breaks the token's invariants, but is still reported
+    e.as_contract(&token.address, || storage::set_cap(&e, 900));
+    assert!(token.max_supply_reached());
+    assert_eq!(token.remaining_mintable(), -100);
+}
+
+#[test]
+fn test_auto_burn() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let fee_recipient = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    assert_eq!(token.auto_burn_rate(), 0);
+
+    token.set_auto_burn_rate(&500);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_auto_burn_rate"),
+                    (500_u32,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.auto_burn_rate(), 500);
+
+    token.transfer(&user1, &user2, &1000);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("burn"), user1.clone()).into_val(&e),
+                50_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), user1.clone(), user2.clone()).into_val(&e),
+                950_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 9000);
+    assert_eq!(token.balance(&user2), 950);
+    assert_eq!(token.total_supply(), 9950);
+
+    // burns are withheld alongside transfer fees
+    token.set_transfer_fee(&1000, &fee_recipient);
+    assert_eq!(
+        token.simulate_balance_after_transfer(&user1, &user2, &1000),
+        (8000, 1800)
+    );
+    let receipt = token.transfer_detailed(&user1, &user2, &1000);
+    assert_eq!(receipt.net_amount, 850);
+    assert_eq!(receipt.fee, 150);
+    assert_eq!(token.balance(&user2), 1800);
+    assert_eq!(token.balance(&fee_recipient), 100);
+    assert_eq!(token.total_supply(), 9900);
+
+    // the fee and burn cannot exceed the transfer amount
+    let result = token.try_set_transfer_fee(&9600, &fee_recipient);
+    assert_eq!(
+        result,
+        Err(Ok(TokenError::OperationNotSupportedError.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn auto_burn_rate_over_max() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.set_auto_burn_rate(&1001);
+}
+
+#[test]
+fn test_holders() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let user3 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    assert_eq!(token.holders(&0, &10), vec![&e]);
+
+    token.mint(&user1, &1000);
+    token.mint(&user2, &2000);
+    token.transfer(&user1, &user3, &400);
+    assert_eq!(
+        token.holders(&0, &2),
+        vec![&e, (user1.clone(), 600), (user2.clone(), 2000)]
+    );
+    assert_eq!(token.holders(&1, &2), vec![&e, (user3.clone(), 400)]);
+    assert_eq!(token.holders(&2, &2), vec![&e]);
+    assert_eq!(token.holders(&u32::MAX, &u32::MAX), vec![&e]);
+
+    // the last holder replaces a holder whose balance reaches zero
+    token.transfer(&user1, &user2, &600);
+    assert_eq!(
+        token.holders(&0, &10),
+        vec![&e, (user3.clone(), 400), (user2.clone(), 2600)]
+    );
+
+    token.burn(&user2, &2600);
+    assert_eq!(token.holders(&0, &10), vec![&e, (user3.clone(), 400)]);
+
+    token.mint(&user1, &100);
+    assert_eq!(
+        token.holders(&0, &10),
+        vec![&e, (user3.clone(), 400), (user1.clone(), 100)]
+    );
+    assert_eq!(token.stats().total_holders, 2);
+}
+
+#[test]
+fn test_set_name_and_symbol() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    let name = String::from_str(&e, "new name");
+    let symbol = String::from_str(&e, "NEW");
+
+    token.set_name(&name);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("set_name"),
+                    (name.clone(),).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (Symbol::new(&e, "name_changed"), admin.clone()).into_val(&e),
+                (String::from_str(&e, "name"), name.clone()).into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.name(), name);
+
+    token.set_symbol(&symbol);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (Symbol::new(&e, "symbol_changed"), admin.clone()).into_val(&e),
+                (String::from_str(&e, "symbol"), symbol.clone()).into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.symbol(), symbol);
+
+    let too_long = Err(Ok(TokenError::MetadataTooLongError.into()));
+    assert_eq!(
+        token.try_set_name(&String::from_str(&e, &"n".repeat(33))),
+        too_long
+    );
+    assert_eq!(
+        token.try_set_symbol(&String::from_str(&e, &"S".repeat(13))),
+        too_long
+    );
+    token.set_symbol(&String::from_str(&e, &"S".repeat(12)));
+}
+
+#[test]
+fn test_set_decimals() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.set_decimals(&18);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_decimals"),
+                    (18_u32,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.decimals(), 18);
+
+    token.mint(&user1, &1000);
+    let result = token.try_set_decimals(&7);
+    assert_eq!(
+        result,
+        Err(Ok(TokenError::DecimalsImmutableAfterMintError.into()))
+    );
+    assert_eq!(token.decimals(), 18);
+}
+
+#[test]
+fn test_storage_ttl() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user, &1000);
+    token.approve(&user, &spender, &500, &(100 + 200 * DAY_IN_LEDGERS));
+    e.as_contract(&token.address, || {
+        let key = DataKey::Allowance(AllowanceDataKey {
+            from: user.clone(),
+            spender: spender.clone(),
+        });
+        assert_eq!(e.storage().temporary().get_ttl(&key), 200 * DAY_IN_LEDGERS);
+
+        let key = DataKey::Balance(user.clone());
+        assert_eq!(e.storage().persistent().get_ttl(&key), BALANCE_BUMP_AMOUNT);
+    });
+
+    // the allowance is still bumped to its expiration after being partially spent
+    e.ledger().set_sequence_number(100 + DAY_IN_LEDGERS);
+    token.transfer_from(&spender, &user, &spender, &100);
+    e.as_contract(&token.address, || {
+        let key = DataKey::Allowance(AllowanceDataKey {
+            from: user.clone(),
+            spender: spender.clone(),
+        });
+        assert_eq!(e.storage().temporary().get_ttl(&key), 199 * DAY_IN_LEDGERS);
+    });
+}
+
+#[test]
+fn test_storage_types() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user, &1000);
+    token.approve(&user, &spender, &500, &1000);
+    e.as_contract(&token.address, || {
+        // metadata lives with the contract instance
+        assert!(e.storage().instance().has(&storage::METADATA_KEY));
+        assert!(!e.storage().persistent().has(&storage::METADATA_KEY));
+
+        // balances are persistent, such that they are archived rather than deleted
+        let key = DataKey::Balance(user.clone());
+        assert!(e.storage().persistent().has(&key));
+        assert!(!e.storage().temporary().has(&key));
+
+        // allowances are temporary, such that they are deleted once expired
+        let key = DataKey::Allowance(AllowanceDataKey {
+            from: user.clone(),
+            spender: spender.clone(),
+        });
+        assert!(e.storage().temporary().has(&key));
+        assert!(!e.storage().persistent().has(&key));
+    });
+}
+
+#[test]
+fn test_migrate_storage() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    token.mint(&user, &1000);
+    assert!(!token.is_migration_needed());
+
+    // storage written by version 1 does not record its version
+    e.as_contract(&token.address, || {
+        e.storage().instance().remove(&symbol_short!("VERSION"));
+    });
+    assert!(token.is_migration_needed());
+
+    let result = token.try_migrate_storage(&2);
+    assert_eq!(
+        result,
+        Err(Ok(TokenError::OperationNotSupportedError.into()))
+    );
+
+    token.migrate_storage(&1);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "migrate_storage"),
+                    (1_u32,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert!(!token.is_migration_needed());
+    assert_eq!(token.balance(&user), 1000);
+
+    // a migration cannot be applied twice
+    let result = token.try_migrate_storage(&1);
+    assert_eq!(
+        result,
+        Err(Ok(TokenError::OperationNotSupportedError.into()))
+    );
+}
+
+#[test]
+fn test_recipient_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let from = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let to = Address::generate(&e);
+    let other = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    token.mint(&from, &1000);
+
+    token.approve_to(&from, &spender, &to, &500, &200);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            from.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "approve_to"),
+                    (&from, &spender, &to, 500_i128, 200_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (Symbol::new(&e, "approve_to"), &from, &spender, &to).into_val(&e),
+                (500_i128, 200_u32).into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(token.recipient_allowance(&from, &spender, &to), 500);
+    assert_eq!(token.allowance(&from, &spender), 0);
+
+    // the allowance cannot be redirected to another recipient, or used by transfer_from
+    let result = token.try_transfer_from_to(&spender, &from, &other, &100);
+    assert_eq!(result, Err(Ok(TokenError::AllowanceError.into())));
+    let result = token.try_transfer_from(&spender, &from, &to, &100);
+    assert_eq!(result, Err(Ok(TokenError::AllowanceError.into())));
+
+    token.transfer_from_to(&spender, &from, &to, &300);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            spender.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "transfer_from_to"),
+                    (&spender, &from, &to, 300_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.recipient_allowance(&from, &spender, &to), 200);
+    assert_eq!(token.balance(&from), 700);
+    assert_eq!(token.balance(&to), 300);
+
+    let result = token.try_transfer_from_to(&spender, &from, &to, &201);
+    assert_eq!(result, Err(Ok(TokenError::AllowanceError.into())));
+
+    e.ledger().set_sequence_number(201);
+    let result = token.try_transfer_from_to(&spender, &from, &to, &100);
+    assert_eq!(result, Err(Ok(TokenError::AllowanceError.into())));
+}
+
+#[test]
+fn test_escrowed_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let from = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let to = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    token.mint(&from, &1000);
+
+    token.approve_escrowed(&from, &spender, &600, &200);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            from.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "approve_escrowed"),
+                    (&from, &spender, 600_i128, 200_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), &from, &token.address).into_val(&e),
+                600_i128.into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(
+        token.escrowed_allowance(&from, &spender),
+        AllowanceInfo {
+            amount: 600,
+            expiration_ledger: 200
+        }
+    );
+    assert_eq!(token.balance(&from), 400);
+    assert_eq!(token.balance(&token.address), 600);
+
+    // the escrow is drawn from even if `from` spends their balance
+    token.transfer(&from, &to, &400);
+    token.transfer_from_escrowed(&spender, &from, &to, &250);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            spender.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "transfer_from_escrowed"),
+                    (&spender, &from, &to, 250_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.escrowed_allowance(&from, &spender).amount, 350);
+    assert_eq!(token.balance(&to), 650);
+    assert_eq!(token.balance(&token.address), 350);
+
+    let result = token.try_transfer_from_escrowed(&spender, &from, &to, &351);
+    assert_eq!(result, Err(Ok(TokenError::AllowanceError.into())));
+
+    // an expired escrow cannot be spent, but can be reclaimed
+    e.ledger().set_sequence_number(201);
+    let result = token.try_transfer_from_escrowed(&spender, &from, &to, &100);
+    assert_eq!(result, Err(Ok(TokenError::AllowanceError.into())));
+
+    token.approve_escrowed(&from, &spender, &0, &0);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), &token.address, &from).into_val(&e),
+                350_i128.into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(token.escrowed_allowance(&from, &spender).amount, 0);
+    assert_eq!(token.balance(&from), 350);
+    assert_eq!(token.balance(&token.address), 0);
+}
+
+#[test]
+fn test_approve_zero_removes_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let from = Address::generate(&e);
+    let spender1 = Address::generate(&e);
+    let spender2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.approve(&from, &spender1, &500, &200);
+    token.approve(&from, &spender2, &500, &200);
+    token.approve(&from, &spender1, &0, &200);
+    e.as_contract(&token.address, || {
+        let key = DataKey::Allowance(AllowanceDataKey {
+            from: from.clone(),
+            spender: spender1.clone(),
+        });
+        assert!(!e.storage().temporary().has(&key));
+        assert_eq!(storage::get_spenders(&e, &from), vec![&e, spender2.clone()]);
+    });
+    assert_eq!(token.allowance(&from, &spender1), 0);
+
+    token.revoke_all_allowances(&from);
+    e.as_contract(&token.address, || {
+        let key = DataKey::Allowance(AllowanceDataKey {
+            from: from.clone(),
+            spender: spender2.clone(),
+        });
+        assert!(!e.storage().temporary().has(&key));
+    });
+    assert_eq!(token.allowance(&from, &spender2), 0);
+}
+
+#[test]
+fn test_revoke_missing_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let from = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    // revoking an allowance that was never set writes nothing and emits no event
+    token.approve(&from, &spender, &0, &0);
+    assert_eq!(e.events().all(), vec![&e]);
+    assert_eq!(token.allowance(&from, &spender), 0);
+    e.as_contract(&token.address, || {
+        let key = DataKey::Allowance(AllowanceDataKey {
+            from: from.clone(),
+            spender: spender.clone(),
+        });
+        assert!(!e.storage().temporary().has(&key));
+    });
+}
+
+#[test]
+fn test_approve_unchanged() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let from = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.approve(&from, &spender, &500, &200);
+    assert_eq!(e.events().all().len(), 1);
+
+    // re-approving the same amount and expiration is a no-op
+    token.approve(&from, &spender, &500, &200);
+    assert_eq!(e.events().all(), vec![&e]);
+    assert_eq!(token.allowance(&from, &spender), 500);
+
+    token.approve(&from, &spender, &500, &300);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("approve"), &from, &spender).into_val(&e),
+                (500_i128, 300_u32).into_val(&e)
+            )
+        ]
+    );
+}
+
+#[test]
+fn test_expired_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let from = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    token.mint(&from, &1000);
+
+    token.approve(&from, &spender, &500, &200);
+    e.ledger().set_sequence_number(200);
+    assert_eq!(token.allowance(&from, &spender), 500);
+
+    // SEP-0041: an expired entry should be treated as a 0 amount allowance, even if
+    // the entry itself outlives its expiration
+    e.as_contract(&token.address, || {
+        let key = DataKey::Allowance(AllowanceDataKey {
+            from: from.clone(),
+            spender: spender.clone(),
+        });
+        e.storage().temporary().extend_ttl(&key, 100, 100);
+    });
+    e.ledger().set_sequence_number(201);
+    assert_eq!(token.allowance(&from, &spender), 0);
+    assert_eq!(
+        token.allowance_pages(&from, &0, &10),
+        vec![
+            &e,
+            (
+                spender.clone(),
+                AllowanceInfo {
+                    amount: 0,
+                    expiration_ledger: 200
+                }
+            )
+        ]
+    );
+    let result = token.try_transfer_from(&spender, &from, &spender, &1);
+    assert_eq!(result, Err(Ok(TokenError::AllowanceError.into())));
+}
+
+#[test]
+fn test_clawback_log() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    token.mint(&user1, &1000);
+    token.mint(&user2, &1000);
+
+    token.clawback(&user1, &100);
+    e.ledger().set_sequence_number(101);
+    token.batch_burn(&vec![&e, (user1.clone(), 200), (user2.clone(), 300)]);
+    assert_eq!(
+        token.clawback_log(&user1, &0, &10),
+        vec![
+            &e,
+            ClawbackEntry {
+                ledger: 101,
+                amount: 200,
+                by_admin: admin.clone(),
+            },
+            ClawbackEntry {
+                ledger: 100,
+                amount: 100,
+                by_admin: admin.clone(),
+            },
+        ]
+    );
+    assert_eq!(token.clawback_log(&user2, &0, &10).len(), 1);
+    assert_eq!(
+        token.clawback_log(&user1, &1, &1).get_unchecked(0).amount,
+        100
+    );
+    assert_eq!(token.clawback_log(&user1, &2, &1).len(), 0);
+
+    // only the most recent entries are kept
+    for _ in 0..DEFAULT_CLAWBACK_LOG_SIZE {
+        token.clawback(&user1, &1);
+    }
+    let log = token.clawback_log(&user1, &0, &100);
+    assert_eq!(log.len(), DEFAULT_CLAWBACK_LOG_SIZE);
+    assert!(log.iter().all(|entry| entry.amount == 1));
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn test_full_snapshot() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.set_cap(&5000);
+    token.mint(&user1, &1000);
+
+    assert_eq!(
+        token.full_snapshot(),
+        crate::contract::MockTokenState {
+            admin: admin.clone(),
+            decimals: 7,
+            name: String::from_str(&e, "name"),
+            symbol: String::from_str(&e, "symbol"),
+            stats: token.stats(),
+            cap: 5000,
+            fee_bps: 0,
+            fee_recipient: None,
+            auto_burn_bps: 0,
+            storage_version: crate::migration::STORAGE_VERSION,
+        }
+    );
+}
+
+#[test]
+fn test_classic_asset_id() {
+    let e = Env::default();
+
+    let admin = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    assert_eq!(token.classic_asset_id(), None);
+}
+
+#[test]
+fn test_large_transfer_event() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    assert_eq!(token.large_transfer_threshold(), None);
+    token.transfer(&user1, &user2, &5000);
+    assert_eq!(e.events().all().len(), 1);
+
+    token.set_large_transfer_threshold(&500);
+    assert_eq!(token.large_transfer_threshold(), Some(500));
+
+    token.transfer(&user1, &user2, &499);
+    assert_eq!(e.events().all().len(), 1);
+
+    token.transfer(&user1, &user2, &500);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), user1.clone(), user2.clone()).into_val(&e),
+                500_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (
+                    Symbol::new(&e, "large_transfer"),
+                    user1.clone(),
+                    user2.clone()
+                )
+                    .into_val(&e),
+                500_i128.into_val(&e)
+            ),
+        ]
+    );
+
+    token.approve(&user1, &spender, &1000, &200);
+    token.transfer_from(&spender, &user1, &user2, &1000);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), user1.clone(), user2.clone()).into_val(&e),
+                1000_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (
+                    Symbol::new(&e, "large_transfer"),
+                    user1.clone(),
+                    user2.clone()
+                )
+                    .into_val(&e),
+                1000_i128.into_val(&e)
+            ),
+        ]
+    );
+
+    let result = token.try_set_large_transfer_threshold(&0);
+    assert_eq!(
+        result,
+        Err(Ok(TokenError::OperationNotSupportedError.into()))
+    );
+}
+
+#[test]
+fn test_locked_supply() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    assert_eq!(token.locked_supply(), 0);
+    assert_eq!(token.circulating_supply(), 10000);
+
+    token.stake(&user1, &1000, &100);
+    let hold_id = token.hold_transfer(&user1, &user2, &2000, &100);
+    token.approve_escrowed(&user1, &spender, &3000, &100);
+    assert_eq!(token.locked_supply(), 6000);
+    assert_eq!(token.circulating_supply(), 4000);
+
+    token.accept_hold(&hold_id);
+    assert_eq!(token.locked_supply(), 4000);
+    assert_eq!(token.circulating_supply(), 6000);
+    assert_eq!(token.total_supply(), 10000);
+}
+
+#[test]
+fn test_sep41_version() {
+    let e = Env::default();
+
+    let admin = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    assert_eq!(token.sep41_version(), String::from_str(&e, SEP41_VERSION));
+}
+
+#[test]
+fn test_initialize_invalid_metadata() {
+    let e = Env::default();
+
+    let admin = Address::generate(&e);
+    let token = MockTokenClient::new(&e, &e.register(MockToken {}, ()));
+    let result = token.try_initialize(
+        &admin,
+        &7,
+        &String::from_bytes(&e, b"name\n"),
+        &"symbol".into_val(&e),
+    );
+    assert_eq!(result, Err(Ok(TokenError::InvalidMetadataError.into())));
+
+    let result = token.try_initialize(
+        &admin,
+        &7,
+        &"name".into_val(&e),
+        &String::from_bytes(&e, &[0x55, 0xC0, 0xAF]),
+    );
+    assert_eq!(result, Err(Ok(TokenError::InvalidMetadataError.into())));
+}
+
+#[test]
+fn test_genesis_ledger() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(12345);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    assert_eq!(token.genesis_ledger(), 12345);
+
+    e.ledger().set_sequence_number(20000);
+    token.mint(&user1, &1000);
+    assert_eq!(token.genesis_ledger(), 12345);
+}
+
+#[test]
+fn test_authorization_policy() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    assert_eq!(
+        token.authorization_policy(),
+        TokenAuthorizationPolicy::FreezeOnly
+    );
+    token.set_authorized(&user2, &false);
+    assert_eq!(
+        token.try_transfer(&user1, &user2, &100),
+        Err(Ok(TokenError::UnauthorizedError.into()))
+    );
+
+    // open tokens ignore authorization
+    token.set_authorization_policy(&TokenAuthorizationPolicy::Open);
+    assert!(token.authorized(&user2));
+    token.transfer(&user1, &user2, &100);
+    assert_eq!(token.balance(&user2), 100);
+
+    // authorization required tokens only accept explicitly authorized addresses
+    token.set_authorization_policy(&TokenAuthorizationPolicy::AuthorizationRequired);
+    assert!(!token.authorized(&user1));
+    assert_eq!(
+        token.try_transfer(&user1, &user2, &100),
+        Err(Ok(TokenError::UnauthorizedError.into()))
+    );
+    token.set_authorized(&user1, &true);
+    token.set_authorized(&user2, &true);
+    token.transfer(&user1, &user2, &100);
+    assert_eq!(token.balance(&user2), 200);
+
+    // admin controlled tokens require the admin to authorize each transfer
+    token.set_authorization_policy(&TokenAuthorizationPolicy::AdminControlled);
+    token.transfer(&user1, &user2, &100);
+    assert_eq!(
+        e.auths(),
+        std::vec![
+            (
+                user1.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        token.address.clone(),
+                        symbol_short!("transfer"),
+                        (&user1, &user2, 100_i128).into_val(&e),
+                    )),
+                    sub_invocations: std::vec![]
+                }
+            ),
+            (
+                admin.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        token.address.clone(),
+                        symbol_short!("transfer"),
+                        (&user1, &user2, 100_i128).into_val(&e),
+                    )),
+                    sub_invocations: std::vec![]
+                }
+            )
+        ]
+    );
+    assert_eq!(token.balance(&user2), 300);
+}
+
+#[test]
+fn test_last_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    assert_eq!(token.last_transfer(&user1, &user2), None);
+
+    token.transfer(&user1, &user2, &100);
+    assert_eq!(token.last_transfer(&user1, &user2), Some((100, 100)));
+    assert_eq!(token.last_transfer(&user2, &user1), None);
+
+    e.ledger().set_sequence_number(110);
+    token.transfer(&user1, &user2, &50);
+    assert_eq!(token.last_transfer(&user1, &user2), Some((110, 50)));
+
+    // the transfer is forgotten once its temporary entry expires
+    e.ledger()
+        .set_sequence_number(110 + storage::LAST_TRANSFER_LIFETIME + 1);
+    assert_eq!(token.last_transfer(&user1, &user2), None);
+}
+
+#[test]
+fn test_transfer_fee_estimate() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let fee_recipient = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    assert_eq!(token.transfer_fee_estimate(&user1, &user2, &1000), 0);
+
+    token.set_transfer_fee(&250, &fee_recipient);
+    assert_eq!(token.transfer_fee_estimate(&user1, &user2, &1000), 25);
+    token.transfer(&user1, &user2, &1000);
+    assert_eq!(token.balance(&fee_recipient), 25);
+
+    token.add_fee_exempt(&user2);
+    assert_eq!(token.transfer_fee_estimate(&user1, &user2, &1000), 0);
+    assert_eq!(token.transfer_fee_estimate(&user2, &user1, &1000), 0);
+}
+
+#[test]
+fn test_safe_approve() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.safe_approve(&user1, &spender, &0, &500, &200);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "safe_approve"),
+                    (&user1, &spender, 0_i128, 500_i128, 200_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("approve"), user1.clone(), spender.clone()).into_val(&e),
+                (500_i128, 200_u32).into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.allowance(&user1, &spender), 500);
+
+    // the spender front-runs the change
+    token.transfer_from(&spender, &user1, &user2, &500);
+    assert_eq!(
+        token.try_safe_approve(&user1, &spender, &500, &100, &200),
+        Err(Ok(TokenError::AllowanceMismatchError.into()))
+    );
+    assert_eq!(token.allowance(&user1, &spender), 0);
+
+    token.safe_approve(&user1, &spender, &0, &100, &200);
+    assert_eq!(token.allowance(&user1, &spender), 100);
+}
+
+#[test]
+fn test_min_balance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    assert_eq!(token.min_balance(&user1), 0);
+    assert_eq!(token.spendable_balance(&user1), 1000);
+
+    token.set_min_balance(&user1, &300);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "set_min_balance"),
+                    (&user1, 300_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.min_balance(&user1), 300);
+    assert_eq!(token.spendable_balance(&user1), 700);
+
+    assert_eq!(
+        token.try_transfer(&user1, &user2, &701),
+        Err(Ok(TokenError::BalanceError.into()))
+    );
+    assert_eq!(
+        token.try_burn(&user1, &701),
+        Err(Ok(TokenError::BalanceError.into()))
+    );
+    token.transfer(&user1, &user2, &700);
+    assert_eq!(token.balance(&user1), 300);
+    assert_eq!(token.spendable_balance(&user1), 0);
+
+    // the admin can still claw back reserved balances
+    token.clawback(&user1, &300);
+    assert_eq!(token.balance(&user1), 0);
+    assert_eq!(token.spendable_balance(&user1), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn simulate_balance_after_transfer_below_min_balance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &1000);
+    token.set_min_balance(&user1, &300);
+    token.simulate_balance_after_transfer(&user1, &user2, &701);
+}
+
+#[test]
+fn test_burn_fee() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let fee_recipient = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.mint(&user1, &10000);
+    token.set_burn_fee_bps(&250);
+    assert_eq!(token.burn_fee_bps(), 250);
+
+    // no fee is charged without a fee recipient
+    token.burn(&user1, &1000);
+    assert_eq!(token.total_burned(), 1000);
+
+    token.set_transfer_fee(&0, &fee_recipient);
+    token.burn(&user1, &1000);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    symbol_short!("transfer"),
+                    user1.clone(),
+                    fee_recipient.clone()
+                )
+                    .into_val(&e),
+                25_i128.into_val(&e)
+            ),
+            (
+                token.address.clone(),
+                (symbol_short!("burn"), user1.clone()).into_val(&e),
+                975_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 8000);
+    assert_eq!(token.balance(&fee_recipient), 25);
+    assert_eq!(token.total_burned(), 1975);
+    assert_eq!(token.stats().total_supply, 8025);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn burn_fee_over_max() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.set_burn_fee_bps(&501);
+}
+
+#[test]
+fn test_extend_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.approve(&user1, &spender, &500, &200);
+    token.extend_allowance(&user1, &spender, &1000);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "extend_allowance"),
+                    (&user1, &spender, 1000_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("approve"), user1.clone(), spender.clone()).into_val(&e),
+                (500_i128, 1000_u32).into_val(&e)
+            ),
+        ]
+    );
+
+    e.ledger().set_sequence_number(500);
+    assert_eq!(token.allowance(&user1, &spender), 500);
+
+    e.ledger().set_sequence_number(1001);
+    assert_eq!(
+        token.try_extend_allowance(&user1, &spender, &2000),
+        Err(Ok(TokenError::AllowanceExpiredError.into()))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #22)")]
+fn extend_allowance_cannot_approve() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    token.approve(&user1, &spender, &500, &200);
+    token.set_permissions(
+        &user1,
+        &TokenPermissions {
+            can_send: true,
+            can_receive: true,
+            can_burn: true,
+            can_approve: false,
+        },
+    );
+    token.extend_allowance(&user1, &spender, &1000);
+}
+
+#[test]
+fn test_holder_since() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    assert_eq!(token.holder_since(&user1), None);
+    token.mint(&user1, &1000);
+    assert_eq!(token.holder_since(&user1), Some(100));
+
+    e.ledger().set_sequence_number(200);
+    token.transfer(&user1, &user2, &1000);
+    assert_eq!(token.holder_since(&user2), Some(200));
+
+    // the first receipt is kept when the balance returns to 0 and back
+    e.ledger().set_sequence_number(300);
+    token.transfer(&user2, &user1, &1000);
+    assert_eq!(token.holder_since(&user1), Some(100));
+    assert_eq!(token.holder_since(&user2), Some(200));
+}
+
+#[test]
+fn test_periodic_airdrop() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+    let period = token.airdrop_period_length();
+
+    assert_eq!(
+        token.try_claim_airdrop(&user1),
+        Err(Ok(TokenError::AirdropNotFoundError.into()))
+    );
+    token.register_airdrop_recipient(&user1, &100);
+
+    token.claim_airdrop(&user1);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "claim_airdrop"),
+                    (&user1,).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("mint"), admin.clone(), user1.clone()).into_val(&e),
+                100_i128.into_val(&e)
+            ),
+        ]
+    );
+    assert_eq!(token.balance(&user1), 100);
+
+    e.ledger().set_sequence_number(100 + period - 1);
+    assert_eq!(
+        token.try_claim_airdrop(&user1),
+        Err(Ok(TokenError::AirdropNotClaimableError.into()))
+    );
+
+    e.ledger().set_sequence_number(100 + period);
+    token.claim_airdrop(&user1);
+    assert_eq!(token.balance(&user1), 200);
+    assert_eq!(token.stats().total_supply, 200);
+
+    token.deregister_airdrop_recipient(&user1);
+    e.ledger().set_sequence_number(100 + 2 * period);
+    assert_eq!(
+        token.try_claim_airdrop(&user1),
+        Err(Ok(TokenError::AirdropNotFoundError.into()))
+    );
+}
+
+#[test]
+fn test_set_authorized_unchanged() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let token = create_token(&e, &admin);
+
+    // addresses are authorized by default
+    token.set_authorized(&user1, &true);
+    assert_eq!(e.events().all(), vec![&e]);
+
+    token.set_authorized(&user1, &false);
+    assert_eq!(e.events().all().len(), 1);
+    token.set_authorized(&user1, &false);
+    assert_eq!(e.events().all(), vec![&e]);
+    assert!(!token.authorized(&user1));
+
+    token.bulk_set_authorized(&vec![&e, (user1.clone(), false), (user1.clone(), true)]);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (
+                    Symbol::new(&e, "set_authorized"),
+                    admin.clone(),
+                    user1.clone()
+                )
+                    .into_val(&e),
+                true.into_val(&e)
+            ),
+        ]
+    );
+    assert!(token.authorized(&user1));
+}
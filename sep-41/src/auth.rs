@@ -0,0 +1,71 @@
+//! Authorization helpers that require the same arguments as the SEP-0041 functions, and
+//! per-sender nonces for replay protection
+
+use soroban_sdk::{symbol_short, Address, Env, IntoVal, Symbol};
+
+const NONCE_KEY: Symbol = symbol_short!("NONCE");
+
+/// The number of ledgers a nonce lives for once it is used, about 120 days
+const NONCE_BUMP_AMOUNT: u32 = 120 * 17280;
+const NONCE_LIFETIME_THRESHOLD: u32 = NONCE_BUMP_AMOUNT - 20 * 17280;
+
+/// Require authorization by `from` for the arguments of `Token::transfer`,
+/// `(from, to, amount)`.
+///
+/// Within `transfer` itself this is equivalent to `from.require_auth()`. Use it
+/// from other entry points that should be authorized exactly like a transfer, such
+/// that a signature for one cannot be used for different arguments.
+pub fn require_transfer_auth(env: &Env, from: &Address, to: &Address, amount: i128) {
+    from.require_auth_for_args((from.clone(), to.clone(), amount).into_val(env));
+}
+
+/// Require authorization by `from` for the arguments of `Token::approve`,
+/// `(from, spender, amount, live_until_ledger)`.
+pub fn require_approve_auth(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128,
+    live_until_ledger: u32,
+) {
+    from.require_auth_for_args(
+        (from.clone(), spender.clone(), amount, live_until_ledger).into_val(env),
+    );
+}
+
+/// Require authorization by `from` for the arguments of `Token::burn`, `(from, amount)`.
+pub fn require_burn_auth(env: &Env, from: &Address, amount: i128) {
+    from.require_auth_for_args((from.clone(), amount).into_val(env));
+}
+
+/// Fetch the current nonce of `sender`, the number of signed operations by `sender` that
+/// were executed. Returns 0 for senders without any.
+///
+/// Nonces are kept in persistent storage under `("NONCE", sender)`, such that they are
+/// never lost and an old signed operation can never be replayed.
+pub fn read_nonce(env: &Env, sender: &Address) -> u64 {
+    let key = (NONCE_KEY, sender.clone());
+    match env.storage().persistent().get(&key) {
+        Some(nonce) => {
+            env.storage().persistent().extend_ttl(
+                &key,
+                NONCE_LIFETIME_THRESHOLD,
+                NONCE_BUMP_AMOUNT,
+            );
+            nonce
+        }
+        None => 0,
+    }
+}
+
+/// Increment the nonce of `sender`. Call this once a signed operation by `sender` that
+/// included the current nonce has been verified, such that it cannot be executed again.
+pub fn increment_nonce(env: &Env, sender: &Address) {
+    let key = (NONCE_KEY, sender.clone());
+    env.storage()
+        .persistent()
+        .set(&key, &(read_nonce(env, sender) + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, NONCE_LIFETIME_THRESHOLD, NONCE_BUMP_AMOUNT);
+}
@@ -0,0 +1,93 @@
+//! Batches of token operations, collected before they are executed
+
+use soroban_sdk::{contracttype, Address, Env, Error, Vec};
+
+use crate::{client_ext::TokenClientExt, TokenClient};
+
+/// A single operation against a token
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TokenOperation {
+    /// Transfer `(from, to, amount)`
+    Transfer(Address, Address, i128),
+    /// Approve `(from, spender, amount, live_until_ledger)`
+    Approve(Address, Address, i128, u32),
+    /// Burn `(from, amount)`
+    Burn(Address, i128),
+}
+
+/// A sequence of token operations that can be inspected before it is executed.
+///
+/// The batch only holds the operations, and does not invoke the token until it is
+/// simulated.
+#[derive(Clone)]
+pub struct TokenBatch {
+    pub operations: Vec<TokenOperation>,
+}
+
+impl TokenBatch {
+    pub fn new(env: &Env) -> Self {
+        TokenBatch {
+            operations: Vec::new(env),
+        }
+    }
+
+    /// Add a transfer of `amount` from `from` to `to`
+    pub fn add_transfer(&mut self, from: &Address, to: &Address, amount: i128) -> &mut Self {
+        self.operations
+            .push_back(TokenOperation::Transfer(from.clone(), to.clone(), amount));
+        self
+    }
+
+    /// Add an approval of `amount` for `spender` to spend from `from`
+    pub fn add_approve(
+        &mut self,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+        live_until_ledger: u32,
+    ) -> &mut Self {
+        self.operations.push_back(TokenOperation::Approve(
+            from.clone(),
+            spender.clone(),
+            amount,
+            live_until_ledger,
+        ));
+        self
+    }
+
+    /// Add a burn of `amount` from `from`
+    pub fn add_burn(&mut self, from: &Address, amount: i128) -> &mut Self {
+        self.operations
+            .push_back(TokenOperation::Burn(from.clone(), amount));
+        self
+    }
+
+    /// Execute each operation against `token` in order with a try-call, and return the
+    /// outcome of each.
+    ///
+    /// A failed operation does not stop the batch, and successful operations are not
+    /// reverted, so run the batch in a test or simulation environment to inspect it
+    /// without applying it.
+    ///
+    /// # Arguments
+    ///
+    /// - `token` - The address of the token
+    pub fn simulate(&self, env: &Env, token: &Address) -> Vec<Result<(), Error>> {
+        let client = TokenClient::new(env, token);
+        let mut results = Vec::new(env);
+        for operation in self.operations.iter() {
+            let result = match operation {
+                TokenOperation::Transfer(from, to, amount) => {
+                    client.transfer_checked(&from, &to, &amount)
+                }
+                TokenOperation::Approve(from, spender, amount, live_until_ledger) => {
+                    client.approve_checked(&from, &spender, &amount, &live_until_ledger)
+                }
+                TokenOperation::Burn(from, amount) => client.burn_checked(&from, &amount),
+            };
+            results.push_back(result);
+        }
+        results
+    }
+}
@@ -0,0 +1,49 @@
+//! Token events encoded for consumption by EVM bridges
+
+use soroban_sdk::{symbol_short, xdr::ToXdr, Address, Bytes, Env};
+
+/// The length of an ABI encoded word
+const WORD_LEN: u32 = 32;
+
+pub struct BridgeTokenEvents {}
+
+// Bridge events share the SEP-0041 topic layouts, so are published directly like `TokenEvents`
+#[allow(deprecated)]
+impl BridgeTokenEvents {
+    /// Emitted when an amount is transferred from one address to another
+    ///
+    /// Emits the same topics as `TokenEvents::transfer`, but the data is ABI encoded as
+    /// `(bytes32 from, bytes32 to, uint256 amount)` so EVM relayers can decode it without
+    /// custom logic. Addresses are encoded as their 32 byte ed25519 public key or contract
+    /// ID, as they do not fit in an EVM `address`.
+    ///
+    /// Panics if `amount` is negative, as it cannot be encoded as a `uint256`.
+    ///
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[abi_encoded: Bytes]`
+    pub fn transfer(env: &Env, from: Address, to: Address, amount: i128) {
+        let mut data = encode_address(env, &from);
+        data.append(&encode_address(env, &to));
+        data.append(&encode_amount(env, amount));
+        let topics = (symbol_short!("transfer"), from, to);
+        env.events().publish(topics, data);
+    }
+}
+
+/// ABI encode an address as a `bytes32` word containing its 32 byte key
+fn encode_address(env: &Env, address: &Address) -> Bytes {
+    // the XDR encoding of an address ends with its 32 byte key for both accounts and contracts
+    let xdr = address.clone().to_xdr(env);
+    xdr.slice(xdr.len() - WORD_LEN..)
+}
+
+/// ABI encode a non-negative amount as a big endian `uint256` word. Panics if `amount` is
+/// negative.
+fn encode_amount(env: &Env, amount: i128) -> Bytes {
+    if amount < 0 {
+        panic!("bridge events cannot encode a negative amount");
+    }
+    let mut word = [0u8; WORD_LEN as usize];
+    word[16..].copy_from_slice(&amount.to_be_bytes());
+    Bytes::from_array(env, &word)
+}
@@ -0,0 +1,159 @@
+//! Non-panicking extensions to `TokenClient`
+
+use soroban_sdk::{
+    xdr::{ScErrorCode, ScErrorType},
+    Address, ConversionError, Error, InvokeError,
+};
+
+use crate::{FeeTokenClient, PausableTokenClient, StellarAssetClient, TokenClient};
+
+/// Checked versions of the functions of `TokenClient`.
+///
+/// Each function invokes the token with a try-call and returns the error it failed
+/// with, or a default value for queries, rather than panicking, so a single failed
+/// operation does not revert the outer transaction.
+pub trait TokenClientExt {
+    /// Transfer `amount` from `from` to `to`. Returns the error if the transfer fails.
+    fn transfer_checked(&self, from: &Address, to: &Address, amount: &i128) -> Result<(), Error>;
+
+    /// Transfer `amount` from `from` to `to`, consuming the allowance of `spender`.
+    /// Returns the error if the transfer fails.
+    fn transfer_from_checked(
+        &self,
+        spender: &Address,
+        from: &Address,
+        to: &Address,
+        amount: &i128,
+    ) -> Result<(), Error>;
+
+    /// Set the allowance by `amount` for `spender` to transfer/burn from `from`.
+    /// Returns the error if the approval fails.
+    fn approve_checked(
+        &self,
+        from: &Address,
+        spender: &Address,
+        amount: &i128,
+        live_until_ledger: &u32,
+    ) -> Result<(), Error>;
+
+    /// Burn `amount` from `from`. Returns the error if the burn fails.
+    fn burn_checked(&self, from: &Address, amount: &i128) -> Result<(), Error>;
+
+    /// Burn `amount` from `from`, consuming the allowance of `spender`. Returns the
+    /// error if the burn fails.
+    fn burn_from_checked(
+        &self,
+        spender: &Address,
+        from: &Address,
+        amount: &i128,
+    ) -> Result<(), Error>;
+
+    /// Claw back `amount` from `from` with `StellarAssetExtension::clawback`. Returns the
+    /// error if the clawback fails, such as for a token that does not support clawbacks.
+    fn clawback_checked(&self, from: &Address, amount: &i128) -> Result<(), Error>;
+
+    /// Returns the balance of `id`, or None if the query fails, such as for an address
+    /// that is not a token.
+    fn balance_checked(&self, id: &Address) -> Option<i128>;
+
+    /// Returns the allowance for `spender` to transfer from `from`, or 0 if the query
+    /// fails, such as for a token that panics on allowances that were never set.
+    fn allowance_or_zero(&self, from: &Address, spender: &Address) -> i128;
+
+    /// Returns the fee that would be charged on a transfer of `amount` from `from` to
+    /// `to`, or 0 if the token does not implement `FeeToken`.
+    fn transfer_fee_estimate(&self, from: &Address, to: &Address, amount: &i128) -> i128;
+
+    /// Returns true if the token is paused, or false if the token does not implement
+    /// `PausableToken`.
+    fn is_paused(&self) -> bool;
+}
+
+/// Flatten the result of a try-call that returns no value into the error it failed with
+fn flatten(
+    result: Result<Result<(), ConversionError>, Result<Error, InvokeError>>,
+) -> Result<(), Error> {
+    match result {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(conversion_error)) => Err(conversion_error.into()),
+        Err(Ok(error)) => Err(error),
+        Err(Err(InvokeError::Contract(code))) => Err(Error::from_contract_error(code)),
+        Err(Err(InvokeError::Abort)) => Err(Error::from_type_and_code(
+            ScErrorType::Context,
+            ScErrorCode::InvalidAction,
+        )),
+    }
+}
+
+impl TokenClientExt for TokenClient<'_> {
+    fn transfer_checked(&self, from: &Address, to: &Address, amount: &i128) -> Result<(), Error> {
+        flatten(self.try_transfer(from, to, amount))
+    }
+
+    fn transfer_from_checked(
+        &self,
+        spender: &Address,
+        from: &Address,
+        to: &Address,
+        amount: &i128,
+    ) -> Result<(), Error> {
+        flatten(self.try_transfer_from(spender, from, to, amount))
+    }
+
+    fn approve_checked(
+        &self,
+        from: &Address,
+        spender: &Address,
+        amount: &i128,
+        live_until_ledger: &u32,
+    ) -> Result<(), Error> {
+        flatten(self.try_approve(from, spender, amount, live_until_ledger))
+    }
+
+    fn burn_checked(&self, from: &Address, amount: &i128) -> Result<(), Error> {
+        flatten(self.try_burn(from, amount))
+    }
+
+    fn burn_from_checked(
+        &self,
+        spender: &Address,
+        from: &Address,
+        amount: &i128,
+    ) -> Result<(), Error> {
+        flatten(self.try_burn_from(spender, from, amount))
+    }
+
+    fn clawback_checked(&self, from: &Address, amount: &i128) -> Result<(), Error> {
+        flatten(StellarAssetClient::new(&self.env, &self.address).try_clawback(from, amount))
+    }
+
+    fn balance_checked(&self, id: &Address) -> Option<i128> {
+        match self.try_balance(id) {
+            Ok(Ok(balance)) => Some(balance),
+            _ => None,
+        }
+    }
+
+    fn allowance_or_zero(&self, from: &Address, spender: &Address) -> i128 {
+        match self.try_allowance(from, spender) {
+            Ok(Ok(allowance)) => allowance,
+            _ => 0,
+        }
+    }
+
+    fn transfer_fee_estimate(&self, from: &Address, to: &Address, amount: &i128) -> i128 {
+        match FeeTokenClient::new(&self.env, &self.address)
+            .try_transfer_fee_estimate(from, to, amount)
+        {
+            Ok(Ok(fee)) => fee,
+            _ => 0,
+        }
+    }
+
+    fn is_paused(&self) -> bool {
+        matches!(
+            PausableTokenClient::new(&self.env, &self.address).try_is_paused(),
+            Ok(Ok(true))
+        )
+    }
+}
@@ -0,0 +1,248 @@
+//! Fluent construction of the SEP-0041 events, such that each argument is named at the
+//! call site, and filters for selecting them from an event log
+
+use soroban_sdk::{symbol_short, Address, Env, Symbol, TryFromVal, Val, Vec};
+
+/// The SEP-0041 events that can be built by a `TokenEventBuilder`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EventKind {
+    Approve,
+    Transfer,
+    Burn,
+    Mint,
+    Clawback,
+    SetAuthorized,
+}
+
+/// Builder for a SEP-0041 event, where each field of the event is set by name.
+///
+/// ```ignore
+/// TokenEventBuilder::approve()
+///     .from(from)
+///     .to(spender)
+///     .amount(amount)
+///     .expiry(live_until_ledger)
+///     .emit(&env);
+/// ```
+///
+/// `emit` panics if a field required by the event was not set.
+#[derive(Clone, Debug)]
+pub struct TokenEventBuilder {
+    kind: EventKind,
+    admin: Option<Address>,
+    from: Option<Address>,
+    to: Option<Address>,
+    amount: Option<i128>,
+    expiry: Option<u32>,
+    authorize: Option<bool>,
+}
+
+impl TokenEventBuilder {
+    fn new(kind: EventKind) -> Self {
+        TokenEventBuilder {
+            kind,
+            admin: None,
+            from: None,
+            to: None,
+            amount: None,
+            expiry: None,
+            authorize: None,
+        }
+    }
+
+    /// An `approve` event. Requires `from`, `to` (the spender), `amount`, and `expiry`.
+    pub fn approve() -> Self {
+        Self::new(EventKind::Approve)
+    }
+
+    /// A `transfer` event. Requires `from`, `to`, and `amount`.
+    pub fn transfer() -> Self {
+        Self::new(EventKind::Transfer)
+    }
+
+    /// A `burn` event. Requires `from` and `amount`.
+    pub fn burn() -> Self {
+        Self::new(EventKind::Burn)
+    }
+
+    /// A `mint` event. Requires `admin`, `to`, and `amount`.
+    pub fn mint() -> Self {
+        Self::new(EventKind::Mint)
+    }
+
+    /// A `clawback` event. Requires `admin`, `from`, and `amount`.
+    pub fn clawback() -> Self {
+        Self::new(EventKind::Clawback)
+    }
+
+    /// A `set_authorized` event. Requires `admin`, `to` (the address whose authorization
+    /// is set), and `authorize`.
+    pub fn set_authorized() -> Self {
+        Self::new(EventKind::SetAuthorized)
+    }
+
+    pub fn admin(mut self, admin: Address) -> Self {
+        self.admin = Some(admin);
+        self
+    }
+
+    pub fn from(mut self, from: Address) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn to(mut self, to: Address) -> Self {
+        self.to = Some(to);
+        self
+    }
+
+    pub fn amount(mut self, amount: i128) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    /// Set the ledger the allowance of an `approve` event lives until
+    pub fn expiry(mut self, live_until_ledger: u32) -> Self {
+        self.expiry = Some(live_until_ledger);
+        self
+    }
+
+    pub fn authorize(mut self, authorize: bool) -> Self {
+        self.authorize = Some(authorize);
+        self
+    }
+
+    /// Publish the event. Panics if a field required by the event was not set.
+    // The SEP-0041 event layouts predate `#[contractevent]`, so events are still published directly
+    #[allow(deprecated)]
+    pub fn emit(self, env: &Env) {
+        let events = env.events();
+        match self.kind {
+            EventKind::Approve => events.publish(
+                (
+                    symbol_short!("approve"),
+                    required(self.from, "from"),
+                    required(self.to, "to"),
+                ),
+                (
+                    required(self.amount, "amount"),
+                    required(self.expiry, "expiry"),
+                ),
+            ),
+            EventKind::Transfer => events.publish(
+                (
+                    symbol_short!("transfer"),
+                    required(self.from, "from"),
+                    required(self.to, "to"),
+                ),
+                required(self.amount, "amount"),
+            ),
+            EventKind::Burn => events.publish(
+                (symbol_short!("burn"), required(self.from, "from")),
+                required(self.amount, "amount"),
+            ),
+            EventKind::Mint => events.publish(
+                (
+                    symbol_short!("mint"),
+                    required(self.admin, "admin"),
+                    required(self.to, "to"),
+                ),
+                required(self.amount, "amount"),
+            ),
+            EventKind::Clawback => events.publish(
+                (
+                    symbol_short!("clawback"),
+                    required(self.admin, "admin"),
+                    required(self.from, "from"),
+                ),
+                required(self.amount, "amount"),
+            ),
+            EventKind::SetAuthorized => events.publish(
+                (
+                    Symbol::new(env, "set_authorized"),
+                    required(self.admin, "admin"),
+                    required(self.to, "to"),
+                ),
+                required(self.authorize, "authorize"),
+            ),
+        }
+    }
+}
+
+/// Unwrap a field required by the event being emitted
+fn required<T>(field: Option<T>, name: &str) -> T {
+    match field {
+        Some(value) => value,
+        None => panic!("event is missing the required field `{}`", name),
+    }
+}
+
+/// The types of token events that can be selected by an `EventFilter`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TokenEventType {
+    Transfer,
+    Approve,
+    Mint,
+    Burn,
+    Clawback,
+    SetAdmin,
+    SetAuthorized,
+}
+
+impl TokenEventType {
+    /// Returns the name of the event, which is published as its first topic
+    pub fn name(&self) -> &'static str {
+        match self {
+            TokenEventType::Transfer => "transfer",
+            TokenEventType::Approve => "approve",
+            TokenEventType::Mint => "mint",
+            TokenEventType::Burn => "burn",
+            TokenEventType::Clawback => "clawback",
+            TokenEventType::SetAdmin => "set_admin",
+            TokenEventType::SetAuthorized => "set_authorized",
+        }
+    }
+}
+
+/// Criteria for selecting token events, such as when replaying the events of a token to
+/// reconstruct its state.
+///
+/// An event matches if it is of `event_type`, has `address` as one of its topics, and
+/// was published between `from_ledger` and `to_ledger` inclusive. Criteria that are None
+/// match every event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventFilter {
+    pub event_type: Option<TokenEventType>,
+    pub address: Option<Address>,
+    pub from_ledger: u32,
+    pub to_ledger: u32,
+}
+
+impl EventFilter {
+    /// Returns true if an event with `topics` published in `ledger` matches the filter
+    ///
+    /// # Arguments
+    ///
+    /// - `ledger` - The ledger the event was published in
+    /// - `topics` - The topics of the event, starting with its name
+    pub fn matches(&self, env: &Env, ledger: u32, topics: &Vec<Val>) -> bool {
+        if ledger < self.from_ledger || ledger > self.to_ledger {
+            return false;
+        }
+        if let Some(event_type) = self.event_type {
+            let name = topics
+                .first()
+                .and_then(|topic| Symbol::try_from_val(env, &topic).ok());
+            if name != Some(Symbol::new(env, event_type.name())) {
+                return false;
+            }
+        }
+        match &self.address {
+            Some(address) => topics
+                .iter()
+                .skip(1)
+                .any(|topic| Address::try_from_val(env, &topic).as_ref() == Ok(address)),
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,306 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::{
+    events::{EventFilter, TokenEventBuilder, TokenEventType},
+    testutils::apply_filter,
+    TokenEvents,
+};
+use soroban_sdk::{
+    contract, testutils::Address as _, testutils::Events, testutils::Ledger, Address, Env, IntoVal,
+    String, Symbol, TryFromVal, Val, Vec,
+};
+
+/// The type of a topic or data value of an event
+#[derive(Debug)]
+enum Schema {
+    Address,
+    Bool,
+    I128,
+    String,
+    U32,
+    U64,
+    Void,
+    Tuple(&'static [Schema]),
+}
+
+/// The expected layout of each `TokenEvents` event, as `(name, topics, data)`, where
+/// `topics` excludes the leading event name
+const EVENT_SCHEMAS: &[(&str, &[Schema], Schema)] = &[
+    (
+        "approve",
+        &[Schema::Address, Schema::Address],
+        Schema::Tuple(&[Schema::I128, Schema::U32]),
+    ),
+    (
+        "transfer",
+        &[Schema::Address, Schema::Address],
+        Schema::I128,
+    ),
+    ("burn", &[Schema::Address], Schema::I128),
+    ("mint", &[Schema::Address, Schema::Address], Schema::I128),
+    (
+        "clawback",
+        &[Schema::Address, Schema::Address],
+        Schema::I128,
+    ),
+    (
+        "set_authorized",
+        &[Schema::Address, Schema::Address],
+        Schema::Bool,
+    ),
+    ("propose_admin", &[Schema::Address], Schema::Address),
+    ("cancel_proposal", &[Schema::Address], Schema::U64),
+    ("migrate", &[Schema::Address, Schema::Address], Schema::I128),
+    (
+        "approve_to",
+        &[Schema::Address, Schema::Address, Schema::Address],
+        Schema::Tuple(&[Schema::I128, Schema::U32]),
+    ),
+    (
+        "name_changed",
+        &[Schema::Address],
+        Schema::Tuple(&[Schema::String, Schema::String]),
+    ),
+    (
+        "symbol_changed",
+        &[Schema::Address],
+        Schema::Tuple(&[Schema::String, Schema::String]),
+    ),
+    (
+        "fee_exempt_added",
+        &[Schema::Address, Schema::Address],
+        Schema::Void,
+    ),
+    (
+        "fee_exempt_removed",
+        &[Schema::Address, Schema::Address],
+        Schema::Void,
+    ),
+    (
+        "large_transfer",
+        &[Schema::Address, Schema::Address],
+        Schema::I128,
+    ),
+];
+
+#[contract]
+struct EventEmitter;
+
+fn matches_schema(e: &Env, val: Val, schema: &Schema) -> bool {
+    match schema {
+        Schema::Address => Address::try_from_val(e, &val).is_ok(),
+        Schema::Bool => bool::try_from_val(e, &val).is_ok(),
+        Schema::I128 => i128::try_from_val(e, &val).is_ok(),
+        Schema::String => String::try_from_val(e, &val).is_ok(),
+        Schema::U32 => u32::try_from_val(e, &val).is_ok(),
+        Schema::U64 => u64::try_from_val(e, &val).is_ok(),
+        Schema::Void => val.is_void(),
+        Schema::Tuple(schemas) => match Vec::<Val>::try_from_val(e, &val) {
+            Ok(vals) => {
+                vals.len() as usize == schemas.len()
+                    && vals
+                        .iter()
+                        .zip(schemas.iter())
+                        .all(|(val, schema)| matches_schema(e, val, schema))
+            }
+            Err(_) => false,
+        },
+    }
+}
+
+/// Emit `event` from a contract and verify the event matches the schema of `name`
+fn verify_event_schema(e: &Env, name: &str, event: impl FnOnce(&Env)) {
+    let contract = e.register(EventEmitter, ());
+    e.as_contract(&contract, || event(e));
+
+    let (_, topic_schemas, data_schema) = EVENT_SCHEMAS
+        .iter()
+        .find(|(schema_name, _, _)| *schema_name == name)
+        .unwrap_or_else(|| panic!("no schema for event {}", name));
+    let events = e.events().all();
+    assert_eq!(events.len(), 1, "{} emitted {} events", name, events.len());
+    let (address, topics, data) = events.get_unchecked(0);
+    assert_eq!(address, contract);
+
+    assert_eq!(
+        topics.len() as usize,
+        topic_schemas.len() + 1,
+        "{} has the wrong number of topics",
+        name
+    );
+    assert_eq!(
+        Symbol::try_from_val(e, &topics.get_unchecked(0)),
+        Ok(Symbol::new(e, name)),
+        "{} has the wrong event name",
+        name
+    );
+    for (i, schema) in topic_schemas.iter().enumerate() {
+        assert!(
+            matches_schema(e, topics.get_unchecked(i as u32 + 1), schema),
+            "{} topic {} is not {:?}",
+            name,
+            i + 1,
+            schema
+        );
+    }
+    assert!(
+        matches_schema(e, data, data_schema),
+        "{} data is not {:?}",
+        name,
+        data_schema
+    );
+}
+
+#[test]
+fn test_event_schemas() {
+    let e = Env::default();
+    let a = Address::generate(&e);
+    let b = Address::generate(&e);
+    let c = Address::generate(&e);
+    let old = String::from_str(&e, "old");
+    let new = String::from_str(&e, "new");
+
+    verify_event_schema(&e, "approve", |e| {
+        TokenEvents::approve(e, a.clone(), b.clone(), 100, 1000)
+    });
+    verify_event_schema(&e, "transfer", |e| {
+        TokenEvents::transfer(e, a.clone(), b.clone(), 100)
+    });
+    verify_event_schema(&e, "burn", |e| TokenEvents::burn(e, a.clone(), 100));
+    verify_event_schema(&e, "mint", |e| {
+        TokenEvents::mint(e, a.clone(), b.clone(), 100)
+    });
+    verify_event_schema(&e, "clawback", |e| {
+        TokenEvents::clawback(e, a.clone(), b.clone(), 100)
+    });
+    verify_event_schema(&e, "set_authorized", |e| {
+        TokenEvents::set_authorized(e, a.clone(), b.clone(), true)
+    });
+    verify_event_schema(&e, "propose_admin", |e| {
+        TokenEvents::propose_admin(e, a.clone(), b.clone())
+    });
+    verify_event_schema(&e, "cancel_proposal", |e| {
+        TokenEvents::cancel_proposal(e, a.clone(), 1)
+    });
+    verify_event_schema(&e, "migrate", |e| {
+        TokenEvents::migrate(e, a.clone(), b.clone(), 100)
+    });
+    verify_event_schema(&e, "approve_to", |e| {
+        TokenEvents::approve_to(e, a.clone(), b.clone(), c.clone(), 100, 1000)
+    });
+    verify_event_schema(&e, "name_changed", |e| {
+        TokenEvents::name_changed(e, a.clone(), old.clone(), new.clone())
+    });
+    verify_event_schema(&e, "symbol_changed", |e| {
+        TokenEvents::symbol_changed(e, a.clone(), old.clone(), new.clone())
+    });
+    verify_event_schema(&e, "fee_exempt_added", |e| {
+        TokenEvents::fee_exempt_added(e, a.clone(), b.clone())
+    });
+    verify_event_schema(&e, "fee_exempt_removed", |e| {
+        TokenEvents::fee_exempt_removed(e, a.clone(), b.clone())
+    });
+    verify_event_schema(&e, "large_transfer", |e| {
+        TokenEvents::large_transfer(e, a.clone(), b.clone(), 100)
+    });
+}
+
+#[test]
+fn test_event_schema_rejects_wrong_data() {
+    let e = Env::default();
+    let (_, _, transfer_data) = EVENT_SCHEMAS
+        .iter()
+        .find(|(name, _, _)| *name == "transfer")
+        .unwrap();
+
+    assert!(matches_schema(&e, 100i128.into_val(&e), transfer_data));
+    assert!(!matches_schema(&e, 100u32.into_val(&e), transfer_data));
+    assert!(!matches_schema(
+        &e,
+        (100i128, 100i128).into_val(&e),
+        transfer_data
+    ));
+}
+
+#[test]
+fn test_event_builder() {
+    let e = Env::default();
+    let from = Address::generate(&e);
+    let spender = Address::generate(&e);
+
+    verify_event_schema(&e, "approve", |e| {
+        TokenEventBuilder::approve()
+            .expiry(1000)
+            .amount(100)
+            .to(spender.clone())
+            .from(from.clone())
+            .emit(e)
+    });
+}
+
+#[test]
+#[should_panic(expected = "event is missing the required field `expiry`")]
+fn test_event_builder_missing_field() {
+    let e = Env::default();
+    let contract = e.register(EventEmitter, ());
+
+    e.as_contract(&contract, || {
+        TokenEventBuilder::approve()
+            .from(Address::generate(&e))
+            .to(Address::generate(&e))
+            .amount(100)
+            .emit(&e)
+    });
+}
+
+#[test]
+fn test_event_filter() {
+    let e = Env::default();
+    e.ledger().set_sequence_number(100);
+    let a = Address::generate(&e);
+    let b = Address::generate(&e);
+    let c = Address::generate(&e);
+    let contract = e.register(EventEmitter, ());
+    e.as_contract(&contract, || {
+        TokenEvents::transfer(&e, a.clone(), b.clone(), 100);
+        TokenEvents::transfer(&e, b.clone(), c.clone(), 50);
+        TokenEvents::burn(&e, a.clone(), 10);
+    });
+    let events = e.events().all();
+
+    let filter = EventFilter {
+        event_type: Some(TokenEventType::Transfer),
+        address: Some(a.clone()),
+        from_ledger: 0,
+        to_ledger: u32::MAX,
+    };
+    let topics: Vec<Val> = (Symbol::new(&e, "transfer"), a.clone(), b.clone()).into_val(&e);
+    assert_eq!(
+        apply_filter(events.clone(), &filter),
+        Vec::from_array(&e, [(contract.clone(), topics, 100i128.into_val(&e))])
+    );
+
+    let filter = EventFilter {
+        event_type: None,
+        address: Some(a.clone()),
+        ..filter
+    };
+    assert_eq!(apply_filter(events.clone(), &filter).len(), 2);
+
+    let filter = EventFilter {
+        event_type: Some(TokenEventType::Mint),
+        address: None,
+        ..filter
+    };
+    assert_eq!(apply_filter(events.clone(), &filter).len(), 0);
+
+    // events are published in the current ledger
+    let filter = EventFilter {
+        event_type: None,
+        address: None,
+        from_ledger: 101,
+        to_ledger: u32::MAX,
+    };
+    assert_eq!(apply_filter(events, &filter).len(), 0);
+}
@@ -0,0 +1,120 @@
+//! Fractional amounts of tokens, for tokens representing sub-unit ownership of an asset
+
+use core::{cmp::Ordering, fmt};
+
+use soroban_sdk::{Address, Env};
+
+use crate::{TokenClient, MAX_DECIMALS};
+
+/// The minimum decimals of a token representing fractional ownership
+pub const MIN_FRACTIONAL_DECIMALS: u32 = 18;
+
+/// The maximum number of decimal places shown when displaying a `FractionalAmount`
+const MAX_DISPLAY_DECIMALS: u32 = 18;
+
+/// An amount of tokens with `decimals` decimal places, such that `FractionalAmount(5, 1)`
+/// is half of a token.
+///
+/// Amounts with different decimals compare by value, so `FractionalAmount(5, 1)` equals
+/// `FractionalAmount(50, 2)`. Amounts that cannot be rescaled without overflowing, such as
+/// amounts with more than `MAX_DECIMALS` decimals, are not comparable.
+#[derive(Clone, Copy, Debug)]
+pub struct FractionalAmount(pub i128, pub u32);
+
+impl FractionalAmount {
+    /// Returns the amount rescaled to `decimals`, or None if it overflows or would lose
+    /// precision
+    pub fn rescale(&self, decimals: u32) -> Option<FractionalAmount> {
+        if decimals >= self.1 {
+            let scalar = 10i128.checked_pow(decimals - self.1)?;
+            Some(FractionalAmount(self.0.checked_mul(scalar)?, decimals))
+        } else {
+            let scalar = 10i128.checked_pow(self.1 - decimals)?;
+            if self.0 % scalar != 0 {
+                return None;
+            }
+            Some(FractionalAmount(self.0 / scalar, decimals))
+        }
+    }
+
+    /// Returns the sum of two amounts with the larger of their decimals, or None if it
+    /// overflows
+    pub fn checked_add(&self, other: &FractionalAmount) -> Option<FractionalAmount> {
+        let (a, b) = self.align(other)?;
+        Some(FractionalAmount(a.0.checked_add(b.0)?, a.1))
+    }
+
+    /// Returns the difference of two amounts with the larger of their decimals, or None if
+    /// it overflows
+    pub fn checked_sub(&self, other: &FractionalAmount) -> Option<FractionalAmount> {
+        let (a, b) = self.align(other)?;
+        Some(FractionalAmount(a.0.checked_sub(b.0)?, a.1))
+    }
+
+    /// Rescale both amounts to the larger of their decimals
+    fn align(&self, other: &FractionalAmount) -> Option<(FractionalAmount, FractionalAmount)> {
+        let decimals = self.1.max(other.1);
+        Some((self.rescale(decimals)?, other.rescale(decimals)?))
+    }
+}
+
+impl PartialEq for FractionalAmount {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for FractionalAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let (a, b) = self.align(other)?;
+        Some(a.0.cmp(&b.0))
+    }
+}
+
+impl fmt::Display for FractionalAmount {
+    /// Formats the amount as a decimal number, with up to 18 decimal places and no
+    /// trailing zeros, truncating any further places
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 < 0 {
+            f.write_str("-")?;
+        }
+        let magnitude = self.0.unsigned_abs();
+        let scalar = 10u128.pow(self.1.min(MAX_DECIMALS));
+        let (whole, fraction) = if self.1 > MAX_DECIMALS {
+            (0, magnitude)
+        } else {
+            (magnitude / scalar, magnitude % scalar)
+        };
+        write!(f, "{}", whole)?;
+
+        let shown = self.1.min(MAX_DISPLAY_DECIMALS);
+        // an i128 has fewer than 39 digits, so truncating more places than that leaves 0
+        let truncated = self.1 - shown;
+        let mut fraction = if truncated > MAX_DECIMALS {
+            0
+        } else {
+            fraction / 10u128.pow(truncated)
+        };
+        if fraction == 0 {
+            return Ok(());
+        }
+        let mut width = shown as usize;
+        while fraction % 10 == 0 {
+            fraction /= 10;
+            width -= 1;
+        }
+        write!(f, ".{:0width$}", fraction, width = width)
+    }
+}
+
+/// Fetch the balance of `id` in `token` as a `FractionalAmount` with the decimals of the
+/// token.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+/// - `id` - The address for which a balance is being queried
+pub fn fractional_balance(env: &Env, token: &Address, id: &Address) -> FractionalAmount {
+    let client = TokenClient::new(env, token);
+    FractionalAmount(client.balance(id), client.decimals())
+}
@@ -1,18 +1,64 @@
 //! Interface for SEP-41 Token Standard
 //! https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0041.md
+//!
+//! # Conventions
+//!
+//! Functions of the `Token` trait and its extensions order their arguments as:
+//!
+//! 1. `env`
+//! 2. The address that must authorize the call, such as the `spender` of `transfer_from`
+//!    or the `from` of `transfer`
+//! 3. `from`, the address whose balance or allowance is drawn from, if it is not the
+//!    address authorizing the call
+//! 4. `to`, or the `spender` being approved
+//! 5. `amount`
+//! 6. Any remaining options, such as a `live_until_ledger`
+//!
+//! Functions with more arguments than clippy's `too_many_arguments` limit should group
+//! related arguments into a `#[contracttype]` struct rather than allowing the lint.
 
 #![no_std]
 
 #[cfg(any(test, feature = "testutils"))]
 pub mod testutils;
 
-use soroban_sdk::{contractclient, symbol_short, Address, Env, String};
+pub mod auth;
+pub mod batch;
+pub mod bridge_events;
+pub mod client_ext;
+pub mod events;
+pub mod fractional;
+pub mod math;
+pub mod oracle;
+pub mod receiver;
+pub mod utils;
+pub mod validate;
+pub mod watcher;
+
+mod events_test;
+mod test;
+
+use events::TokenEventBuilder;
+use soroban_sdk::{
+    contractclient, contracttrait, contracttype, symbol_short, Address, Bytes, Env, String, Symbol,
+    Vec,
+};
+
+/// The version of the SEP-0041 specification implemented by the `Token` trait
+pub const SEP41_VERSION: &str = "0.4.0";
+
+/// The maximum decimals of a token, as `10^38` is the largest power of 10 an `i128` can
+/// hold. Amounts of tokens with more decimals cannot be scaled to whole tokens.
+pub const MAX_DECIMALS: u32 = 38;
 
 /// SEP-0041 Token Standard Trait
 #[contractclient(name = "TokenClient")]
 pub trait Token {
     /// Returns the allowance for `spender` to transfer from `from`.
     ///
+    /// An allowance whose `live_until_ledger` is less than the current ledger number
+    /// has expired, and is returned as 0.
+    ///
     /// # Arguments
     ///
     /// - `from` - The address holding the balance of tokens to be drawn from.
@@ -22,16 +68,19 @@ pub trait Token {
     /// Set the allowance by `amount` for `spender` to transfer/burn from
     /// `from`. Overrides any existing allowance set between `spender` and `from`.
     ///
+    /// Setting the amount to 0 for a `spender` without an allowance succeeds, and
+    /// implementations may skip the storage write and event, as the allowance is unchanged.
+    ///
     /// # Arguments
     ///
     /// - `from` - The address holding the balance of tokens to be drawn from.
     /// - `spender` - The address being authorized to spend the tokens held by
-    /// `from`.
+    ///   `from`.
     /// - `amount` - The tokens to be made available to `spender`.
     /// - `live_until_ledger` - The ledger number where this allowance expires.
-    /// Cannot be less than the current ledger number unless the amount is being
-    /// set to 0.  An expired entry (where live_until_ledger < the current
-    /// ledger number) should be treated as a 0 amount allowance.
+    ///   Cannot be less than the current ledger number unless the amount is being
+    ///   set to 0.  An expired entry (where live_until_ledger < the current
+    ///   ledger number) should be treated as a 0 amount allowance.
     ///
     /// # Events
     ///
@@ -48,7 +97,7 @@ pub trait Token {
     /// # Arguments
     ///
     /// - `id` - The address for which a balance is being queried. If the
-    /// address has no existing balance, returns 0.
+    ///   address has no existing balance, returns 0.
     fn balance(env: Env, id: Address) -> i128;
 
     /// Transfer `amount` from `from` to `to`.
@@ -56,7 +105,7 @@ pub trait Token {
     /// # Arguments
     ///
     /// - `from` - The address holding the balance of tokens which will be
-    /// withdrawn from.
+    ///   withdrawn from.
     /// - `to` - The address which will receive the transferred tokens.
     /// - `amount` - The amount of tokens to be transferred.
     ///
@@ -70,12 +119,19 @@ pub trait Token {
     /// Transfer `amount` from `from` to `to`, consuming the allowance of
     /// `spender`. Authorized by spender (`spender.require_auth()`).
     ///
+    /// The allowance for `spender` from `from` is decremented by `amount`; if the
+    /// resulting allowance is 0, the entry may be deleted from storage.
+    ///
+    /// An `amount` of 0 is valid and moves no tokens, but still requires the
+    /// authorization of `spender`. As with `transfer`, implementations may emit a
+    /// `transfer` event with an amount of 0.
+    ///
     /// # Arguments
     ///
     /// - `spender` - The address authorizing the transfer, and having its
-    /// allowance consumed during the transfer.
+    ///   allowance consumed during the transfer.
     /// - `from` - The address holding the balance of tokens which will be
-    /// withdrawn from.
+    ///   withdrawn from.
     /// - `to` - The address which will receive the transferred tokens.
     /// - `amount` - The amount of tokens to be transferred.
     ///
@@ -91,7 +147,7 @@ pub trait Token {
     /// # Arguments
     ///
     /// - `from` - The address holding the balance of tokens which will be
-    /// burned from.
+    ///   burned from.
     /// - `amount` - The amount of tokens to be burned.
     ///
     /// # Events
@@ -102,13 +158,18 @@ pub trait Token {
     fn burn(env: Env, from: Address, amount: i128);
 
     /// Burn `amount` from `from`, consuming the allowance of `spender`.
+    /// Authorized by spender (`spender.require_auth()`), and not by `from`.
+    ///
+    /// The allowance for `spender` from `from` must not have expired and must be at
+    /// least `amount`. It is decremented by `amount`; if the resulting allowance is 0,
+    /// the entry may be deleted from storage.
     ///
     /// # Arguments
     ///
     /// - `spender` - The address authorizing the burn, and having its allowance
-    /// consumed during the burn.
+    ///   consumed during the burn.
     /// - `from` - The address holding the balance of tokens which will be
-    /// burned from.
+    ///   burned from.
     /// - `amount` - The amount of tokens to be burned.
     ///
     /// # Events
@@ -116,9 +177,14 @@ pub trait Token {
     /// Emits an event with:
     /// - topics - `["burn", from: Address]`
     /// - data - `[amount: i128]`
+    ///
+    /// This is the same event emitted by `burn`, and can be published with
+    /// [`TokenEvents::burn`]. No `approve` event is emitted for the decremented allowance.
     fn burn_from(env: Env, spender: Address, from: Address, amount: i128);
 
-    /// Returns the number of decimals used to represent amounts of this token.
+    /// Returns the number of decimals used to represent amounts of this token. Should not
+    /// exceed `MAX_DECIMALS`, which implementations can enforce with
+    /// `validate::validate_decimals`.
     fn decimals(env: Env) -> u32;
 
     /// Returns the name for this token.
@@ -159,7 +225,8 @@ pub trait StellarAssetExtension {
     /// - `addr` - The address which will have their authorization status queried.
     fn authorized(env: Env, addr: Address) -> bool;
 
-    /// Clawback `amount` of tokens from `from`.
+    /// Clawback `amount` of tokens from `from`. `from` can be an account or a contract
+    /// address.
     ///
     /// Requires authorization by the admin.
     ///
@@ -182,48 +249,1565 @@ pub trait StellarAssetExtension {
     fn admin(env: Env);
 }
 
-pub struct TokenEvents {}
+/// The kind of asset a token represents
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TokenType {
+    /// A plain fungible token
+    Fungible,
+    /// A token wrapping another asset
+    Wrapped,
+    /// A token tracking the value of another asset without holding it
+    Synthetic,
+    /// A token granting governance rights
+    Governance,
+    /// A token whose balances adjust to track a target supply or price
+    RebaseToken,
+    /// Any other kind of token, identified by name
+    Other(Symbol),
+}
 
-impl TokenEvents {
-    /// Emitted when an allowance is set
+/// The maximum length of a token URI, in bytes
+pub const MAX_TOKEN_URI_LEN: u32 = 256;
+
+/// The maximum length of a token name set through `AdminToken`, in bytes
+pub const MAX_TOKEN_NAME_LEN: u32 = 32;
+
+/// The maximum length of a token symbol set through `AdminToken`, in bytes
+pub const MAX_TOKEN_SYMBOL_LEN: u32 = 12;
+
+/// Extension for tokens that expose metadata beyond the SEP-0041 `decimals`, `name`,
+/// and `symbol`. All metadata is informational and has no effect on token behavior.
+#[contractclient(name = "ExtendedMetadataTokenClient")]
+pub trait ExtendedMetadataToken {
+    /// Returns the kind of asset this token represents.
     ///
-    /// - topics - `["approve", from: Address, spender: Address]`
-    /// - data - `[amount: i128, live_until_ledger: u32]`
-    pub fn approve(
-        env: &Env,
-        from: Address,
-        spender: Address,
-        amount: i128,
-        live_until_ledger: u32,
-    ) {
-        let topics = (symbol_short!("approve"), from, spender);
-        env.events().publish(topics, (amount, live_until_ledger));
-    }
+    /// The token type is set when the token is initialized and cannot be changed.
+    fn token_type(env: Env) -> TokenType;
 
-    /// Emitted when an amount is transferred from one address to another
+    /// Returns the URI of an off-chain JSON document describing this token, if set.
+    fn token_uri(env: Env) -> Option<String>;
+
+    /// Set the URI of an off-chain JSON document describing this token.
     ///
-    /// - topics - `["transfer", from: Address, to: Address]`
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `uri` - The URI of the document, using the `ipfs://`, `https://`, or `data:`
+    ///   scheme. The content is not validated, but cannot exceed `MAX_TOKEN_URI_LEN`
+    ///   bytes.
+    fn set_token_uri(env: Env, uri: String);
+}
+
+/// Extension for tokens that can record balance snapshots, such that governance systems
+/// can weigh votes by a balance that cannot be manipulated within the current ledger.
+#[contractclient(name = "SnapshotTokenClient")]
+pub trait SnapshotToken {
+    /// Record the current balance of `id` as a snapshot taken at the current ledger.
+    ///
+    /// Callable by anyone.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address whose balance will be recorded.
+    fn take_balance_snapshot(env: Env, id: Address);
+
+    /// Returns the balance of `id` recorded by the most recent snapshot taken before the
+    /// current ledger. Snapshots taken during the current ledger are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which a snapshot balance is being queried. If the
+    ///   address has no snapshot older than the current ledger, returns 0.
+    fn snapshot_balance(env: Env, id: Address) -> i128;
+}
+
+/// Extension for tokens that charge a fee on transfers. The fee is deducted from the
+/// transferred amount, such that the recipient receives `amount - fee`.
+///
+/// `transfer_from` spends the allowance by the full `amount`, including the fee, as that
+/// is the amount the spender moves out of `from`.
+#[contractclient(name = "FeeTokenClient")]
+pub trait FeeToken {
+    /// Returns the fee charged on transfers, in basis points of the transferred amount.
+    fn transfer_fee_bps(env: Env) -> u32;
+
+    /// Returns the address receiving transfer fees, if a fee is set.
+    fn fee_recipient(env: Env) -> Option<Address>;
+
+    /// Returns the fee that would be charged on a transfer of `amount` from `from` to
+    /// `to`, accounting for fee exemptions, without modifying state. Returns 0 if no fee
+    /// is due.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens which would be withdrawn from
+    /// - `to` - The address which would receive the transferred tokens
+    /// - `amount` - The amount of tokens that would be transferred
+    fn transfer_fee_estimate(env: Env, from: Address, to: Address, amount: i128) -> i128;
+
+    /// Set the fee charged on transfers to `fee_bps` basis points, paid to `recipient`.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `fee_bps` - The fee in basis points. Cannot exceed 10000.
+    /// - `recipient` - The address which will receive transfer fees.
+    fn set_transfer_fee(env: Env, fee_bps: u32, recipient: Address);
+
+    /// Returns the share of burned tokens paid to the fee recipient instead of being
+    /// burned, in basis points of the burned amount.
+    fn burn_fee_bps(env: Env) -> u32;
+
+    /// Pay `fee_bps` basis points of each burn to the fee recipient, such that only the
+    /// rest of the amount is burned. No fee is charged while no fee recipient is set.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `fee_bps` - The fee in basis points. Cannot exceed 500.
+    ///
+    /// # Events
+    ///
+    /// Burns with a fee emit a `transfer` event to the fee recipient for the fee, and a
+    /// `burn` event for the amount burned.
+    fn set_burn_fee_bps(env: Env, fee_bps: u32);
+
+    /// Exempt `id` from transfer fees. Transfers where either `from` or `to` is exempt
+    /// are not charged a fee.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address which will be exempt from transfer fees.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["fee_exempt_added", admin: Address, id: Address]`
+    /// - data - `()`
+    fn add_fee_exempt(env: Env, id: Address);
+
+    /// Remove the transfer fee exemption of `id`.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address which will no longer be exempt from transfer fees.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["fee_exempt_removed", admin: Address, id: Address]`
+    /// - data - `()`
+    fn remove_fee_exempt(env: Env, id: Address);
+
+    /// Returns true if `id` is exempt from transfer fees.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the exemption is being queried.
+    fn is_fee_exempt(env: Env, id: Address) -> bool;
+}
+
+/// Extension for tokens that can issue balances which expire after a ledger deadline,
+/// like promotional credits.
+///
+/// Expiring tokens are included in `balance` until they expire, and are spent before
+/// any other tokens held by the address.
+#[contractclient(name = "ExpiringBalanceTokenClient")]
+pub trait ExpiringBalanceToken {
+    /// Create `amount` of tokens and assign them to `to`, expiring after `expires_at`.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `to` - The address which will receive the created tokens.
+    /// - `amount` - The amount of tokens to be created.
+    /// - `expires_at` - The last ledger number where the tokens can be spent.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["mint", admin: Address, to: Address]`
     /// - data - `[amount: i128]`
-    pub fn transfer(env: &Env, from: Address, to: Address, amount: i128) {
-        let topics = (symbol_short!("transfer"), from, to);
-        env.events().publish(topics, amount);
-    }
+    fn issue_expiring(env: Env, to: Address, amount: i128, expires_at: u32);
 
-    /// Emitted when an amount of tokens is burnt from one address
+    /// Returns the portion of the balance of `id` that will expire.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the expiring balance is being queried.
+    fn expiring_balance(env: Env, id: Address) -> i128;
+
+    /// Burn the expired portion of the balance of `id`. Callable by anyone once the
+    /// expiring tokens have expired.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address which will have their expired tokens burned.
+    ///
+    /// # Events
     ///
+    /// Emits an event with:
     /// - topics - `["burn", from: Address]`
     /// - data - `[amount: i128]`
-    pub fn burn(env: &Env, from: Address, amount: i128) {
-        let topics = (symbol_short!("burn"), from);
-        env.events().publish(topics, amount);
-    }
+    fn expire_account(env: Env, id: Address);
+}
 
-    /// Emitted when an amount of tokens is created and assigned to an address
+/// Extension for tokens that limit the amount of a single transfer.
+///
+/// Zero amount transfers are not subject to the limits.
+#[contractclient(name = "TransferLimitsTokenClient")]
+pub trait TransferLimitsToken {
+    /// Returns the minimum amount of a single transfer or burn. Defaults to 1.
+    fn minimum_transfer(env: Env) -> i128;
+
+    /// Set the minimum amount of a single transfer or burn to `min`, to protect
+    /// against dust attacks.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `min` - The minimum amount. Must be positive, and cannot exceed the maximum
+    ///   transfer.
+    fn set_minimum_transfer(env: Env, min: i128);
+
+    /// Returns the maximum amount of a single transfer. Defaults to `i128::MAX`.
+    fn maximum_transfer(env: Env) -> i128;
+
+    /// Set the maximum amount of a single transfer to `max`.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `max` - The maximum amount. Cannot be less than the minimum transfer.
+    fn set_maximum_transfer(env: Env, max: i128);
+
+    /// Limit the amount `id` can transfer out to `limit` within each window of
+    /// `window_ledgers` ledgers. The first window starts when the limit is set, and each
+    /// following window starts with the first transfer after the previous one ended.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address which will have their outbound transfers limited.
+    /// - `limit` - The maximum amount transferred out within a window. Cannot be negative.
+    /// - `window_ledgers` - The number of ledgers in a window. Must be positive.
+    fn set_account_transfer_limit(env: Env, id: Address, limit: i128, window_ledgers: u32);
+
+    /// Returns the amount `id` has transferred out within the current window.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the transfer volume is being queried. If the
+    ///   address has no transfer limit, returns 0.
+    fn account_transfer_volume(env: Env, id: Address) -> i128;
+}
+
+/// Extension for tokens where the admin allocates tokens that are minted when claimed,
+/// such that the recipient pays for the mint instead of the admin.
+#[contractclient(name = "ClaimMintTokenClient")]
+pub trait ClaimMintToken {
+    /// Allocate `amount` of tokens to be claimed by `to`. Adds to any existing allocation.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `to` - The address which will be able to claim the tokens.
+    /// - `amount` - The amount of tokens to be allocated.
+    fn allocate(env: Env, to: Address, amount: i128);
+
+    /// Mint the tokens allocated to `id` and assign them to `id`, clearing the allocation.
+    ///
+    /// Requires authorization by `id`.
+    ///
+    /// # Arguments
     ///
+    /// - `id` - The address claiming their allocation.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
     /// - topics - `["mint", admin: Address, to: Address]`
     /// - data - `[amount: i128]`
-    pub fn mint(env: &Env, admin: Address, to: Address, amount: i128) {
-        let topics = (symbol_short!("mint"), admin, to);
+    fn claim(env: Env, id: Address);
+
+    /// Returns the amount of tokens allocated to `id` that have not been claimed.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the allocation is being queried.
+    fn claimable(env: Env, id: Address) -> i128;
+}
+
+/// Extension for tokens that can be migrated to a replacement token, by burning tokens
+/// and minting the same amount of the replacement token.
+#[contractclient(name = "BurnAndReplaceTokenClient")]
+pub trait BurnAndReplaceToken {
+    /// Returns the replacement token, if set.
+    fn replacement_token(env: Env) -> Option<Address>;
+
+    /// Set the replacement token to `new_token`.
+    ///
+    /// Requires authorization by the admin. The replacement token must implement
+    /// `StellarAssetExtension::mint` and have this token set as its admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `new_token` - The address of the replacement token.
+    fn set_replacement_token(env: Env, new_token: Address);
+
+    /// Burn `amount` from `from` and mint the same amount of the replacement token to
+    /// `from`.
+    ///
+    /// Requires authorization by `from`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens which will be migrated.
+    /// - `amount` - The amount of tokens to be migrated.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["burn", from: Address]`
+    /// - data - `[amount: i128]`
+    ///
+    /// Emits an event with:
+    /// - topics - `["migrate", from: Address, new_token: Address]`
+    /// - data - `[amount: i128]`
+    fn migrate(env: Env, from: Address, amount: i128);
+}
+
+/// Extension for tokens that report their USD price through a registered
+/// `oracle::PriceOracle`.
+#[contractclient(name = "PricedTokenClient")]
+pub trait PricedToken {
+    /// Set the oracle used to price this token to `oracle`.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `oracle` - The address of a contract implementing `oracle::PriceOracle`.
+    fn set_price_oracle(env: Env, oracle: Address);
+
+    /// Returns the USD price of one whole token, as reported by the oracle for this
+    /// token's `symbol`, in the oracle's decimals. Returns None if no oracle is set or
+    /// the oracle has no current price.
+    fn usd_price(env: Env) -> Option<i128>;
+
+    /// Returns the USD value of `raw_amount` in the oracle's decimals, or None if
+    /// `usd_price` is None.
+    ///
+    /// # Arguments
+    ///
+    /// - `raw_amount` - An amount of tokens, in this token's decimals.
+    fn usd_value_of(env: Env, raw_amount: i128) -> Option<i128>;
+}
+
+/// Extension for tokens that notify contract recipients of a transfer through
+/// `receiver::TokenReceiver`.
+#[contractclient(name = "NotifyingTokenClient")]
+pub trait NotifyingToken {
+    /// Transfer `amount` from `from` to `to`, then invoke `on_receive` on `to` if it is
+    /// a contract. The transfer is reverted if `on_receive` panics.
+    ///
+    /// Emits the same events as `transfer`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens which will be
+    ///   withdrawn from.
+    /// - `to` - The address which will receive the transferred tokens.
+    /// - `amount` - The amount of tokens to be transferred.
+    /// - `data` - Arbitrary data passed to the `on_receive` callback of `to`.
+    fn transfer_and_notify(env: Env, from: Address, to: Address, amount: i128, data: Bytes);
+}
+
+/// An allowance granted to a spender
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AllowanceInfo {
+    /// The amount of tokens the spender is allowed to withdraw
+    pub amount: i128,
+    /// The ledger number at which the allowance expires
+    pub expiration_ledger: u32,
+}
+
+/// Extension for tokens that can enumerate the allowances granted by an address.
+#[contractclient(name = "EnumerableAllowanceTokenClient")]
+pub trait EnumerableAllowanceToken {
+    /// Returns a page of the non-zero allowances granted by `from`, in the order the
+    /// spenders were first approved. Returns an empty vector if `page` is past the end.
+    ///
+    /// Allowances that have expired but were not yet revoked are included, with an
+    /// amount of 0.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens to be drawn from.
+    /// - `page` - The index of the page, starting at 0.
+    /// - `page_size` - The maximum number of allowances in a page.
+    fn allowance_pages(
+        env: Env,
+        from: Address,
+        page: u32,
+        page_size: u32,
+    ) -> Vec<(Address, AllowanceInfo)>;
+
+    /// Returns the number of non-zero allowances granted by `from` that have not
+    /// expired.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens to be drawn from.
+    fn approval_count(env: Env, from: Address) -> u64;
+
+    /// Returns the number of non-zero allowances granted across all addresses.
+    ///
+    /// An allowance is counted until it is spent, set to 0 or revoked, so allowances
+    /// that have expired are still included. Use `approval_count` for an exact count of
+    /// the allowances granted by a single address.
+    fn active_approvals(env: Env) -> u64;
+}
+
+/// Extension for tokens with allowances restricted to a single recipient, such that a
+/// spender cannot redirect the approved tokens to any other address.
+///
+/// Recipient allowances are separate from the allowances set by `approve`, and are only
+/// consumed by `transfer_from_to`.
+#[contractclient(name = "RecipientAllowanceTokenClient")]
+pub trait RecipientAllowanceToken {
+    /// Returns the allowance for `spender` to transfer from `from` to `to`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens to be drawn from.
+    /// - `spender` - The address spending the tokens held by `from`.
+    /// - `to` - The only address the tokens can be transferred to.
+    fn recipient_allowance(env: Env, from: Address, spender: Address, to: Address) -> i128;
+
+    /// Set the allowance by `amount` for `spender` to transfer from `from` to `to`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens to be drawn from.
+    /// - `spender` - The address being authorized to spend the tokens held by `from`.
+    /// - `to` - The only address the tokens can be transferred to.
+    /// - `amount` - The tokens to be made available to `spender`.
+    /// - `live_until_ledger` - The ledger number where this allowance expires. Cannot
+    ///   be less than the current ledger number unless the amount is being set to 0.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["approve_to", from: Address, spender: Address, to: Address]`
+    /// - data - `[amount: i128, live_until_ledger: u32]`
+    fn approve_to(
+        env: Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        amount: i128,
+        live_until_ledger: u32,
+    );
+
+    /// Transfer `amount` from `from` to `to`, consuming the recipient allowance of
+    /// `spender` for `to`. Authorized by spender (`spender.require_auth()`).
+    ///
+    /// # Arguments
+    ///
+    /// - `spender` - The address authorizing the transfer, and having its
+    ///   allowance consumed during the transfer.
+    /// - `from` - The address holding the balance of tokens which will be
+    ///   withdrawn from.
+    /// - `to` - The address which will receive the transferred tokens.
+    /// - `amount` - The amount of tokens to be transferred.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    fn transfer_from_to(env: Env, spender: Address, from: Address, to: Address, amount: i128);
+}
+
+/// Extension for tokens with escrowed allowances, where the approved tokens are locked in
+/// the token contract, such that the spender is guaranteed they are available.
+///
+/// Escrowed allowances are separate from the allowances set by `approve`, and are only
+/// consumed by `transfer_from_escrowed`.
+#[contractclient(name = "EscrowedAllowanceTokenClient")]
+pub trait EscrowedAllowanceToken {
+    /// Returns the escrowed allowance for `spender` to transfer from `from`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address whose tokens are escrowed.
+    /// - `spender` - The address spending the escrowed tokens.
+    fn escrowed_allowance(env: Env, from: Address, spender: Address) -> AllowanceInfo;
+
+    /// Set the escrowed allowance by `amount` for `spender` to transfer from `from`,
+    /// moving the difference from the current escrow between `from` and the token
+    /// contract. An escrow that has expired can be reclaimed by setting it to 0.
+    ///
+    /// Requires authorization by `from`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens to be escrowed.
+    /// - `spender` - The address being authorized to spend the escrowed tokens.
+    /// - `amount` - The tokens to be escrowed for `spender`.
+    /// - `live_until_ledger` - The ledger number where this allowance expires. Cannot
+    ///   be less than the current ledger number unless the amount is being set to 0.
+    ///
+    /// # Events
+    ///
+    /// If the escrow changes, emits an event with:
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    ///
+    /// where `to` is the token contract if tokens are added to the escrow, and `from`
+    /// is the token contract if tokens are returned
+    fn approve_escrowed(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        live_until_ledger: u32,
+    );
+
+    /// Transfer `amount` from the tokens escrowed by `from` to `to`, consuming the
+    /// escrowed allowance of `spender`. Authorized by spender (`spender.require_auth()`).
+    ///
+    /// # Arguments
+    ///
+    /// - `spender` - The address authorizing the transfer, and having its
+    ///   escrowed allowance consumed during the transfer.
+    /// - `from` - The address whose tokens are escrowed.
+    /// - `to` - The address which will receive the transferred tokens.
+    /// - `amount` - The amount of tokens to be transferred.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    ///
+    /// where `from` is the token contract
+    fn transfer_from_escrowed(env: Env, spender: Address, from: Address, to: Address, amount: i128);
+}
+
+/// The kind of operation recorded in an `AuditEntry`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum AuditKind {
+    /// Tokens were transferred to the counterparty
+    Send,
+    /// Tokens were received from the counterparty
+    Receive,
+    /// Tokens were burned. The counterparty is the address that burned them.
+    Burn,
+    /// Tokens were minted. The counterparty is the admin that minted them.
+    Mint,
+    /// An allowance was granted to the counterparty
+    Approve,
+}
+
+/// An operation involving an address, recorded by an `AuditableToken`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AuditEntry {
+    /// The ledger number the operation occurred in
+    pub ledger: u32,
+    /// The kind of operation
+    pub kind: AuditKind,
+    /// The other address involved in the operation
+    pub counterparty: Address,
+    /// The amount of tokens involved in the operation
+    pub amount: i128,
+}
+
+/// The default number of entries an `AuditableToken` retains per address
+pub const DEFAULT_AUDIT_LOG_SIZE: u32 = 10;
+
+/// Extension for tokens that keep an on-chain audit trail of the most recent operations
+/// involving each address.
+///
+/// Entries are kept in temporary storage, so an audit log can expire if the address
+/// has been inactive. Off-chain indexers of the token's events remain the source of
+/// record.
+#[contractclient(name = "AuditableTokenClient")]
+pub trait AuditableToken {
+    /// Returns up to `count` of the most recent operations involving `id`, newest first.
+    ///
+    /// Tokens retain a limited number of entries per address, which defaults to
+    /// `DEFAULT_AUDIT_LOG_SIZE`.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the audit log is being queried.
+    /// - `count` - The maximum number of entries to return.
+    fn audit_log(env: Env, id: Address, count: u32) -> Vec<AuditEntry>;
+}
+
+/// A clawback from an address, recorded by a `ClawbackLogToken`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct ClawbackEntry {
+    /// The ledger number the clawback occurred in
+    pub ledger: u32,
+    /// The amount of tokens clawed back
+    pub amount: i128,
+    /// The admin that performed the clawback
+    pub by_admin: Address,
+}
+
+/// The default number of entries a `ClawbackLogToken` retains per address
+pub const DEFAULT_CLAWBACK_LOG_SIZE: u32 = 10;
+
+/// Extension for tokens that keep an on-chain log of the most recent clawbacks from each
+/// address, independent of any `AuditableToken` log.
+///
+/// Entries are kept in temporary storage, so a clawback log can expire if no clawback
+/// has occurred recently. The `clawback` events of the token remain the source of record.
+#[contractclient(name = "ClawbackLogTokenClient")]
+pub trait ClawbackLogToken {
+    /// Returns a page of the most recent clawbacks from `id`, newest first. Returns an
+    /// empty vector if `page` is past the end.
+    ///
+    /// Tokens retain a limited number of entries per address, which defaults to
+    /// `DEFAULT_CLAWBACK_LOG_SIZE`.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the clawback log is being queried.
+    /// - `page` - The index of the page, starting at 0.
+    /// - `page_size` - The maximum number of entries in a page.
+    fn clawback_log(env: Env, id: Address, page: u32, page_size: u32) -> Vec<ClawbackEntry>;
+}
+
+/// Extension for vote-escrowed tokens, where holders lock tokens in the token contract
+/// in exchange for voting power that decays as the lock approaches its end.
+#[contractclient(name = "StakingTokenClient")]
+pub trait StakingToken {
+    /// Lock `amount` of tokens held by `from` in the token contract until `lock_until`.
+    ///
+    /// Requires authorization by `from`. Panics if `lock_until` is not in the future,
+    /// or exceeds the longest lock the token allows.
+    ///
+    /// Returns the id of the stake
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens to be staked.
+    /// - `amount` - The amount of tokens to be staked.
+    /// - `lock_until` - The ledger number at which the stake can be unstaked.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    ///
+    /// where `to` is the token contract
+    fn stake(env: Env, from: Address, amount: i128, lock_until: u32) -> u64;
+
+    /// Return the tokens locked by the stake `stake_id` to its owner.
+    ///
+    /// Requires authorization by the owner of the stake. Panics if the stake is still
+    /// locked.
+    ///
+    /// # Arguments
+    ///
+    /// - `stake_id` - The id of the stake, as returned by `stake`.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    ///
+    /// where `from` is the token contract
+    fn unstake(env: Env, stake_id: u64);
+
+    /// Returns the amount of tokens staked by `id`, including stakes that are unlocked
+    /// but were not yet unstaked.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the staked balance is being queried.
+    fn staked_balance(env: Env, id: Address) -> i128;
+
+    /// Returns the voting power of `id`, as the sum over its stakes of
+    /// `amount * remaining_lock_ledgers / max_lock_ledgers`, where `max_lock_ledgers` is
+    /// the longest lock the token allows.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the voting power is being queried.
+    fn voting_power(env: Env, id: Address) -> i128;
+}
+
+/// The scalar of the fixed point reward rate of a `StakingRewardsToken`
+pub const REWARD_RATE_SCALAR: i128 = 1_0000000;
+
+/// Extension for `StakingToken`s that reward stakers in proportion to the amount and
+/// duration of their stake. Rewards are minted from a reward pool funded by the admin.
+#[contractclient(name = "StakingRewardsTokenClient")]
+pub trait StakingRewardsToken {
+    /// Set the reward rate for all stakers, from the current ledger onwards.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `rate` - The amount of tokens rewarded per ledger for each staked token, as a
+    ///   fixed point number scaled by `REWARD_RATE_SCALAR`.
+    fn set_reward_rate(env: Env, rate: i128);
+
+    /// Set the amount of tokens that can still be minted as rewards. Rewards are only
+    /// paid out while the pool is not empty.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `amount` - The amount of tokens in the reward pool.
+    fn set_reward_pool(env: Env, amount: i128);
+
+    /// Returns the amount of tokens remaining in the reward pool.
+    fn reward_pool(env: Env) -> i128;
+
+    /// Returns the rewards accrued by `staker` that have not been harvested.
+    ///
+    /// # Arguments
+    ///
+    /// - `staker` - The address for which pending rewards are being queried.
+    fn pending_rewards(env: Env, staker: Address) -> i128;
+
+    /// Mint the pending rewards of `staker` to `staker`, up to the amount remaining in
+    /// the reward pool. Any rewards exceeding the pool remain pending.
+    ///
+    /// Requires authorization by `staker`.
+    ///
+    /// # Arguments
+    ///
+    /// - `staker` - The address harvesting its rewards.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["mint", admin: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    fn harvest_rewards(env: Env, staker: Address);
+}
+
+/// Extension for tokens that can be burned once a condition reported by an
+/// `oracle::OracleCondition` is met, such as insurance tokens burned when a claim is paid.
+#[contractclient(name = "ConditionalBurnTokenClient")]
+pub trait ConditionalBurnToken {
+    /// Burn `amount` from `from` if `condition_oracle` reports the condition described
+    /// by `condition_data` as met. Panics if the condition is not met.
+    ///
+    /// Requires authorization by `from`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens which will be
+    ///   burned from.
+    /// - `amount` - The amount of tokens to be burned.
+    /// - `condition_oracle` - The address of a contract implementing
+    ///   `oracle::OracleCondition`.
+    /// - `condition_data` - The condition passed to `check_condition`.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["burn", from: Address]`
+    /// - data - `[amount: i128]`
+    fn conditional_burn(
+        env: Env,
+        from: Address,
+        amount: i128,
+        condition_oracle: Address,
+        condition_data: Bytes,
+    );
+}
+
+/// Aggregate statistics of a token, for use by explorers and dashboards
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenStats {
+    /// The amount of tokens in circulation
+    pub total_supply: i128,
+    /// The amount of tokens ever minted
+    pub total_minted: i128,
+    /// The amount of tokens ever burned, including tokens clawed back
+    pub total_burned: i128,
+    /// The number of transfers ever made
+    pub total_transfers: u64,
+    /// The number of addresses with a non-zero balance
+    pub total_holders: u64,
+}
+
+/// Extension for tokens that track aggregate statistics on-chain.
+#[contractclient(name = "StatsTokenClient")]
+pub trait StatsToken {
+    /// Returns the aggregate statistics of this token.
+    fn stats(env: Env) -> TokenStats;
+
+    /// Returns the amount of tokens ever burned, including tokens burned on transfer
+    /// and tokens clawed back. This amount never decreases.
+    fn total_burned(env: Env) -> i128;
+}
+
+/// Extension for tokens that support escrowed transfers, which are held by the token
+/// contract until the recipient accepts or rejects them.
+#[contractclient(name = "HoldTransferTokenClient")]
+pub trait HoldTransferToken {
+    /// Lock `amount` of tokens held by `from` in the token contract, until `to` accepts
+    /// or rejects the transfer, or it expires.
+    ///
+    /// Requires authorization by `from`.
+    ///
+    /// Returns the id of the hold
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens which will be
+    ///   withdrawn from.
+    /// - `to` - The address which can accept the transferred tokens.
+    /// - `amount` - The amount of tokens to be transferred.
+    /// - `expiry` - The last ledger number where the hold can be accepted.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    ///
+    /// where `to` is the token contract
+    fn hold_transfer(env: Env, from: Address, to: Address, amount: i128, expiry: u32) -> u64;
+
+    /// Accept the hold `hold_id`, transferring the held tokens to its recipient.
+    ///
+    /// Requires authorization by the recipient of the hold. Panics if the hold expired.
+    ///
+    /// # Arguments
+    ///
+    /// - `hold_id` - The id of the hold, as returned by `hold_transfer`.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    ///
+    /// where `from` is the token contract and `to` is the recipient of the hold
+    fn accept_hold(env: Env, hold_id: u64);
+
+    /// Reject the hold `hold_id`, returning the held tokens to its sender.
+    ///
+    /// Requires authorization by the recipient of the hold.
+    ///
+    /// # Arguments
+    ///
+    /// - `hold_id` - The id of the hold, as returned by `hold_transfer`.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    ///
+    /// where `from` is the token contract and `to` is the sender of the hold
+    fn reject_hold(env: Env, hold_id: u64);
+
+    /// Return the tokens of the expired hold `hold_id` to its sender. Can be invoked
+    /// by anyone. Panics if the hold has not expired.
+    ///
+    /// # Arguments
+    ///
+    /// - `hold_id` - The id of the hold, as returned by `hold_transfer`.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    ///
+    /// where `from` is the token contract and `to` is the sender of the hold
+    fn expire_hold(env: Env, hold_id: u64);
+}
+
+/// The operations an address is permitted to perform with a `PermissionedToken`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenPermissions {
+    /// The address can transfer tokens to other addresses
+    pub can_send: bool,
+    /// The address can be credited with tokens, including by minting
+    pub can_receive: bool,
+    /// The address can burn its tokens, or have them burned by a spender
+    pub can_burn: bool,
+    /// The address can approve spenders
+    pub can_approve: bool,
+}
+
+/// Extension for tokens that restrict the operations specific addresses can perform,
+/// such as an address that can receive but not send tokens. Permissions are checked in
+/// addition to the authorization status of an address.
+#[contractclient(name = "PermissionedTokenClient")]
+pub trait PermissionedToken {
+    /// Set the permissions of `id`.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address whose permissions are being set.
+    /// - `perms` - The permissions of `id`.
+    fn set_permissions(env: Env, id: Address, perms: TokenPermissions);
+
+    /// Returns the permissions of `id`. Addresses whose permissions were never set can
+    /// perform all operations.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which permissions are being queried.
+    fn permissions(env: Env, id: Address) -> TokenPermissions;
+}
+
+/// The outcome of a transfer, including any fee charged by the token
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TransferReceipt {
+    /// The amount of tokens withdrawn from the sender
+    pub gross_amount: i128,
+    /// The amount of tokens credited to the recipient
+    pub net_amount: i128,
+    /// The amount of tokens withheld from the recipient, such as a transfer fee or an
+    /// amount burned on transfer
+    pub fee: i128,
+    /// The balance of the sender after the transfer
+    pub from_balance_after: i128,
+    /// The balance of the recipient after the transfer
+    pub to_balance_after: i128,
+}
+
+/// Extension for tokens that report the outcome of a transfer, such as fee-on-transfer
+/// tokens where the amount received differs from the amount sent.
+#[contractclient(name = "DetailedTransferTokenClient")]
+pub trait DetailedTransferToken {
+    /// Transfer `amount` from `from` to `to`, as `Token::transfer` does.
+    ///
+    /// Returns the outcome of the transfer
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens which will be
+    ///   withdrawn from.
+    /// - `to` - The address which will receive the transferred tokens.
+    /// - `amount` - The amount of tokens to be transferred.
+    ///
+    /// # Events
+    ///
+    /// Emits the same events as `transfer`.
+    fn transfer_detailed(env: Env, from: Address, to: Address, amount: i128) -> TransferReceipt;
+}
+
+/// Extension for tokens with a maximum total supply.
+///
+/// Implement with `#[contractimpl(contracttrait)]` to export the default functions.
+#[contracttrait(client_name = "CappedTokenClient")]
+pub trait CappedToken {
+    /// Returns the maximum total supply of this token.
+    fn cap(env: Env) -> i128;
+
+    /// Set the maximum total supply of this token to `cap`. Panics if `cap` is below
+    /// the current total supply.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `cap` - The maximum total supply.
+    fn set_cap(env: Env, cap: i128);
+
+    /// Returns the amount of tokens in circulation.
+    fn total_supply(env: Env) -> i128;
+
+    /// Returns true if the total supply has reached the cap, such that no more tokens
+    /// can be minted
+    fn max_supply_reached(env: Env) -> bool {
+        Self::total_supply(env.clone()) >= Self::cap(env)
+    }
+
+    /// Returns the amount of tokens that can be minted before the total supply reaches
+    /// the cap, `cap - total_supply`
+    fn remaining_mintable(env: Env) -> i128 {
+        Self::cap(env.clone()) - Self::total_supply(env)
+    }
+}
+
+/// Extension for deflationary tokens that burn a share of every transfer.
+#[contractclient(name = "AutoBurnTokenClient")]
+pub trait AutoBurnToken {
+    /// Returns the share of every transfer that is burned, in basis points.
+    fn auto_burn_rate(env: Env) -> u32;
+
+    /// Set the share of every transfer that is burned to `burn_bps` basis points. The
+    /// burned amount is withheld from the recipient.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `burn_bps` - The share of the transfer amount to burn, in basis points.
+    ///   Cannot exceed 1000.
+    fn set_auto_burn_rate(env: Env, burn_bps: u32);
+}
+
+/// Extension for tokens that can enumerate their holders, such as to initialize the
+/// voting weights of a governance system.
+#[contractclient(name = "EnumerableHoldersTokenClient")]
+pub trait EnumerableHoldersToken {
+    /// Returns a page of the addresses with a non-zero balance and their balances, in
+    /// no particular order. Returns an empty vector if `page` is past the end.
+    ///
+    /// The order of holders can change when a holder's balance reaches zero, so pages
+    /// should be read within a single ledger.
+    ///
+    /// # Arguments
+    ///
+    /// - `page` - The index of the page, starting at 0.
+    /// - `page_size` - The maximum number of holders in a page.
+    fn holders(env: Env, page: u32, page_size: u32) -> Vec<(Address, i128)>;
+}
+
+/// Extension for tokens that expose simple analytics, as tracked by `TokenStats`.
+#[contractclient(name = "TokenAnalyticsClient")]
+pub trait TokenAnalytics {
+    /// Returns the number of addresses with a non-zero balance.
+    fn holder_count(env: Env) -> u64;
+
+    /// Returns the number of transfers ever made.
+    fn transfer_count(env: Env) -> u64;
+
+    /// Returns the ledger sequence the token was initialized in. This never changes
+    /// after initialization.
+    fn genesis_ledger(env: Env) -> u32;
+
+    /// Returns the ledger sequence `id` first received a non-zero balance in, or None if
+    /// `id` has never held the token. This never changes, even if the balance of `id`
+    /// returns to 0.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the first receipt is being queried
+    fn holder_since(env: Env, id: Address) -> Option<u32>;
+}
+
+/// Extension for tokens whose metadata can be updated by the admin, such as when a
+/// token is rebranded.
+#[contractclient(name = "AdminTokenClient")]
+pub trait AdminToken {
+    /// Set the name of this token to `name`.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` - The new name. Cannot exceed `MAX_TOKEN_NAME_LEN` bytes.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["name_changed", admin: Address]`
+    /// - data - `[old_name: String, new_name: String]`
+    fn set_name(env: Env, name: String);
+
+    /// Set the symbol of this token to `symbol`.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `symbol` - The new symbol. Cannot exceed `MAX_TOKEN_SYMBOL_LEN` bytes.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["symbol_changed", admin: Address]`
+    /// - data - `[old_symbol: String, new_symbol: String]`
+    fn set_symbol(env: Env, symbol: String);
+}
+
+/// Extension for tokens that version their storage schema, such that storage written
+/// by an older version of the contract can be migrated after an upgrade.
+#[contractclient(name = "MigratableTokenClient")]
+pub trait MigratableToken {
+    /// Migrate storage from `from_version` to the storage version of the current contract,
+    /// applying each version's migration in order.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `from_version` - The storage version currently stored. Must match the stored
+    ///   version, such that a migration cannot be applied twice.
+    fn migrate_storage(env: Env, from_version: u32);
+
+    /// Returns true if the stored storage version is older than the storage version of
+    /// the current contract.
+    fn is_migration_needed(env: Env) -> bool;
+}
+
+/// Extension for tokens that may wrap a classic Stellar asset, such that contracts can
+/// correlate the token with the asset.
+#[contractclient(name = "AssetLinkedTokenClient")]
+pub trait AssetLinkedToken {
+    /// Returns the classic Stellar asset wrapped by this token, as `"CODE:ISSUER"` like
+    /// the name of a Stellar Asset Contract, or None if the token does not wrap a
+    /// classic asset.
+    fn classic_asset_id(env: Env) -> Option<String>;
+}
+
+/// Extension for tokens that flag unusually large transfers with a `large_transfer`
+/// event, published alongside the `transfer` event.
+#[contractclient(name = "LargeTransferTokenClient")]
+pub trait LargeTransferToken {
+    /// Returns the minimum amount of a transfer flagged as large, or None if transfers
+    /// are never flagged.
+    fn large_transfer_threshold(env: Env) -> Option<i128>;
+
+    /// Flag every transfer of at least `threshold` with a `large_transfer` event.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `threshold` - The minimum amount of a large transfer. Must be positive.
+    fn set_large_transfer_threshold(env: Env, threshold: i128);
+}
+
+/// Extension for tokens that lock balances in the token contract, such as stakes,
+/// holds, or escrows, which do not circulate until they are released.
+#[contractclient(name = "LockedSupplyTokenClient")]
+pub trait LockedSupplyToken {
+    /// Returns the amount of tokens in circulation that are locked.
+    fn locked_supply(env: Env) -> i128;
+
+    /// Returns the amount of tokens in circulation that are not locked,
+    /// `total_supply - locked_supply`.
+    fn circulating_supply(env: Env) -> i128;
+}
+
+/// Extension for tokens where addresses can have a minimum balance, like the base reserve
+/// of a Stellar account, that must remain in the address and cannot be spent.
+#[contractclient(name = "MinBalanceTokenClient")]
+pub trait MinBalanceToken {
+    /// Returns the balance that must remain held by `id`, or 0 if none is reserved.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the minimum balance is being queried
+    fn min_balance(env: Env, id: Address) -> i128;
+
+    /// Returns the balance `id` can transfer or burn, `balance - min_balance`.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address for which the spendable balance is being queried
+    fn spendable_balance(env: Env, id: Address) -> i128;
+
+    /// Set the minimum balance of `id`. Existing balances below the minimum are kept, but
+    /// cannot be spent until they exceed it.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address whose minimum balance is being set
+    /// - `amount` - The minimum balance. Cannot be negative.
+    fn set_min_balance(env: Env, id: Address, amount: i128);
+}
+
+/// Extension for tokens that set allowances with a compare-and-swap, such that a spender
+/// cannot front-run a change of their allowance to spend both the old and new amounts.
+#[contractclient(name = "SafeApproveTokenClient")]
+pub trait SafeApproveToken {
+    /// Set the allowance by `new_amount` for `spender` to transfer/burn from `from`, only
+    /// if the current allowance is `expected_amount`. Panics if the allowance does not
+    /// match, such as if `spender` used it after the change was submitted.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens to be drawn from.
+    /// - `spender` - The address being authorized to spend the tokens held by `from`.
+    /// - `expected_amount` - The current allowance for `spender`.
+    /// - `new_amount` - The tokens to be made available to `spender`.
+    /// - `live_until_ledger` - The ledger number where this allowance expires. Cannot
+    ///   be less than the current ledger number unless the amount is being set to 0.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["approve", from: Address, spender: Address]`
+    /// - data - `[amount: i128, live_until_ledger: u32]`
+    fn safe_approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        expected_amount: i128,
+        new_amount: i128,
+        live_until_ledger: u32,
+    );
+}
+
+/// Extension for tokens that can renew an allowance without changing its amount, such
+/// that the allowance does not need to be reset to 0 and back.
+#[contractclient(name = "ExtendableAllowanceTokenClient")]
+pub trait ExtendableAllowanceToken {
+    /// Set the ledger the allowance for `spender` to transfer/burn from `from` lives
+    /// until, keeping its amount. Panics if the allowance is expired or was never set.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens to be drawn from.
+    /// - `spender` - The address authorized to spend the tokens held by `from`.
+    /// - `new_live_until_ledger` - The ledger number where the allowance will expire.
+    ///   Cannot be less than the current ledger number.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["approve", from: Address, spender: Address]`
+    /// - data - `[amount: i128, live_until_ledger: u32]`
+    fn extend_allowance(env: Env, from: Address, spender: Address, new_live_until_ledger: u32);
+}
+
+/// Extension for tokens that drip newly minted tokens to registered recipients, who can
+/// each claim a fixed amount once per period.
+#[contractclient(name = "PeriodicAirdropTokenClient")]
+pub trait PeriodicAirdropToken {
+    /// Register `id` to claim `amount_per_period` tokens each period. Replaces the amount
+    /// of an existing recipient.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address which will be able to claim tokens.
+    /// - `amount_per_period` - The amount of tokens minted on each claim. Must be
+    ///   positive.
+    fn register_airdrop_recipient(env: Env, id: Address, amount_per_period: i128);
+
+    /// Mint the amount per period of `id` and assign it to `id`. Panics if `id` is not
+    /// registered, or already claimed within the last `airdrop_period_length` ledgers.
+    ///
+    /// Requires authorization by `id`.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address claiming their airdrop.
+    ///
+    /// # Events
+    ///
+    /// Emits an event with:
+    /// - topics - `["mint", admin: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    fn claim_airdrop(env: Env, id: Address);
+
+    /// Stop `id` from claiming further airdrops.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` - The address which will no longer be able to claim tokens.
+    fn deregister_airdrop_recipient(env: Env, id: Address);
+
+    /// Returns the number of ledgers between claims of a recipient.
+    fn airdrop_period_length(env: Env) -> u32;
+}
+
+/// Extension for tokens with signed operations that can be submitted by a relayer, which
+/// include a per-sender nonce such that the relayer cannot replay them.
+///
+/// Implementations can keep nonces with `auth::read_nonce` and `auth::increment_nonce`.
+#[contractclient(name = "NonceTokenClient")]
+pub trait NonceToken {
+    /// Returns the nonce the next signed operation by `sender` must include.
+    ///
+    /// # Arguments
+    ///
+    /// - `sender` - The address for which the nonce is being queried
+    fn nonce(env: Env, sender: Address) -> u64;
+}
+
+/// Extension for tokens that record the most recent transfer between each pair of
+/// addresses, such that contracts can verify a transfer happened without access to
+/// events.
+///
+/// Transfers are kept in temporary storage, so only transfers made in recent ledgers
+/// are available.
+#[contractclient(name = "TransferLogTokenClient")]
+pub trait TransferLogToken {
+    /// Returns the ledger and amount of the most recent transfer from `from` to `to`, or
+    /// None if no transfer was made recently. The amount is the amount received by `to`.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address that sent the transfer
+    /// - `to` - The address that received the transfer
+    fn last_transfer(env: Env, from: Address, to: Address) -> Option<(u32, i128)>;
+}
+
+/// The rules deciding which addresses can send and receive a token
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum TokenAuthorizationPolicy {
+    /// Any address can send and receive the token, and authorization is ignored
+    Open,
+    /// Only addresses explicitly authorized by the admin can send and receive the
+    /// token, like a classic asset with `AUTH_REQUIRED`
+    AuthorizationRequired,
+    /// Any address can send and receive the token until it is deauthorized by the admin
+    FreezeOnly,
+    /// Like `FreezeOnly`, but every transfer also requires authorization by the admin
+    AdminControlled,
+}
+
+/// Extension for tokens that publish the rules deciding who can transfer the token,
+/// such that the security model of the token can be inspected on-chain.
+#[contractclient(name = "AuthorizationPolicyTokenClient")]
+pub trait AuthorizationPolicyToken {
+    /// Returns the authorization policy applied to transfers.
+    fn authorization_policy(env: Env) -> TokenAuthorizationPolicy;
+
+    /// Set the authorization policy applied to transfers. The authorization status of
+    /// each address is kept, but may be interpreted differently by the new policy.
+    ///
+    /// Requires authorization by the admin.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - The new authorization policy
+    fn set_authorization_policy(env: Env, policy: TokenAuthorizationPolicy);
+}
+
+/// Extension for tokens whose operations can be paused by an admin.
+///
+/// Tokens without this extension are never paused. Use `TokenClientExt::is_paused` to
+/// query any token without panicking.
+#[contractclient(name = "PausableTokenClient")]
+pub trait PausableToken {
+    /// Returns true if the token is paused
+    fn is_paused(env: Env) -> bool;
+}
+
+/// Extension for tokens that report the version of SEP-0041 they were compiled against.
+///
+/// Implement with `#[contractimpl(contracttrait)]` to export the default function.
+#[contracttrait(client_name = "VersionedTokenClient")]
+pub trait VersionedToken {
+    /// Returns the version of the SEP-0041 specification implemented by this token.
+    /// Defaults to the `SEP41_VERSION` of the crate the token was compiled with.
+    fn sep41_version(env: Env) -> String {
+        String::from_str(&env, SEP41_VERSION)
+    }
+}
+
+/// Publishes the events of `SEP-0041` and its extensions.
+///
+/// Event data is documented as a list of values. Data with a single value, such as the
+/// `[amount: i128]` of `transfer`, `mint`, `burn`, and `clawback`, is published as the
+/// value itself rather than as a one element vec. Data with several values, such as the
+/// `[amount: i128, live_until_ledger: u32]` of `approve`, is published as a `Vec<Val>`
+/// in the listed order, and `()` data is published as void.
+pub struct TokenEvents {}
+
+// The SEP-0041 event layouts predate `#[contractevent]`, so events are still published directly
+#[allow(deprecated)]
+impl TokenEvents {
+    /// Emitted when an allowance is set
+    ///
+    /// - topics - `["approve", from: Address, spender: Address]`
+    /// - data - `[amount: i128, live_until_ledger: u32]`
+    pub fn approve(
+        env: &Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        live_until_ledger: u32,
+    ) {
+        TokenEventBuilder::approve()
+            .from(from)
+            .to(spender)
+            .amount(amount)
+            .expiry(live_until_ledger)
+            .emit(env);
+    }
+
+    /// Emitted when an amount is transferred from one address to another
+    ///
+    /// - topics - `["transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    pub fn transfer(env: &Env, from: Address, to: Address, amount: i128) {
+        TokenEventBuilder::transfer()
+            .from(from)
+            .to(to)
+            .amount(amount)
+            .emit(env);
+    }
+
+    /// Emitted when an amount of tokens is burnt from one address
+    ///
+    /// - topics - `["burn", from: Address]`
+    /// - data - `[amount: i128]`
+    pub fn burn(env: &Env, from: Address, amount: i128) {
+        TokenEventBuilder::burn()
+            .from(from)
+            .amount(amount)
+            .emit(env);
+    }
+
+    /// Emitted when an amount of tokens is created and assigned to an address
+    ///
+    /// - topics - `["mint", admin: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    pub fn mint(env: &Env, admin: Address, to: Address, amount: i128) {
+        TokenEventBuilder::mint()
+            .admin(admin)
+            .to(to)
+            .amount(amount)
+            .emit(env);
+    }
+
+    /// Emitted when an amount of tokens is clawed back from an address by the admin
+    ///
+    /// - topics - `["clawback", admin: Address, from: Address]`
+    /// - data - `[amount: i128]`
+    pub fn clawback(env: &Env, admin: Address, from: Address, amount: i128) {
+        TokenEventBuilder::clawback()
+            .admin(admin)
+            .from(from)
+            .amount(amount)
+            .emit(env);
+    }
+
+    /// Emitted when the authorization status of an address is set
+    ///
+    /// - topics - `["set_authorized", admin: Address, id: Address]`
+    /// - data - `[authorize: bool]`
+    pub fn set_authorized(env: &Env, admin: Address, id: Address, authorize: bool) {
+        TokenEventBuilder::set_authorized()
+            .admin(admin)
+            .to(id)
+            .authorize(authorize)
+            .emit(env);
+    }
+
+    /// Emitted when the admin proposes a new admin, who must accept the role before the
+    /// admin changes
+    ///
+    /// - topics - `["propose_admin", current_admin: Address]`
+    /// - data - `[proposed_admin: Address]`
+    pub fn propose_admin(env: &Env, current_admin: Address, proposed_admin: Address) {
+        let topics = (Symbol::new(env, "propose_admin"), current_admin);
+        env.events().publish(topics, proposed_admin);
+    }
+
+    /// Emitted when the admin cancels a pending proposal, such as of a new admin
+    ///
+    /// - topics - `["cancel_proposal", admin: Address]`
+    /// - data - `[proposal_id: u64]`
+    pub fn cancel_proposal(env: &Env, admin: Address, proposal_id: u64) {
+        let topics = (Symbol::new(env, "cancel_proposal"), admin);
+        env.events().publish(topics, proposal_id);
+    }
+
+    /// Emitted when an amount of tokens is migrated to a replacement token
+    ///
+    /// - topics - `["migrate", from: Address, new_token: Address]`
+    /// - data - `[amount: i128]`
+    pub fn migrate(env: &Env, from: Address, new_token: Address, amount: i128) {
+        let topics = (symbol_short!("migrate"), from, new_token);
+        env.events().publish(topics, amount);
+    }
+
+    /// Emitted when an allowance restricted to a single recipient is set
+    ///
+    /// - topics - `["approve_to", from: Address, spender: Address, to: Address]`
+    /// - data - `[amount: i128, live_until_ledger: u32]`
+    pub fn approve_to(
+        env: &Env,
+        from: Address,
+        spender: Address,
+        to: Address,
+        amount: i128,
+        live_until_ledger: u32,
+    ) {
+        let topics = (Symbol::new(env, "approve_to"), from, spender, to);
+        env.events().publish(topics, (amount, live_until_ledger));
+    }
+
+    /// Emitted when the name of the token is changed by the admin
+    ///
+    /// - topics - `["name_changed", admin: Address]`
+    /// - data - `[old_name: String, new_name: String]`
+    pub fn name_changed(env: &Env, admin: Address, old_name: String, new_name: String) {
+        let topics = (Symbol::new(env, "name_changed"), admin);
+        env.events().publish(topics, (old_name, new_name));
+    }
+
+    /// Emitted when the symbol of the token is changed by the admin
+    ///
+    /// - topics - `["symbol_changed", admin: Address]`
+    /// - data - `[old_symbol: String, new_symbol: String]`
+    pub fn symbol_changed(env: &Env, admin: Address, old_symbol: String, new_symbol: String) {
+        let topics = (Symbol::new(env, "symbol_changed"), admin);
+        env.events().publish(topics, (old_symbol, new_symbol));
+    }
+
+    /// Emitted when an address is exempted from transfer fees
+    ///
+    /// - topics - `["fee_exempt_added", admin: Address, id: Address]`
+    /// - data - `()`
+    pub fn fee_exempt_added(env: &Env, admin: Address, id: Address) {
+        let topics = (Symbol::new(env, "fee_exempt_added"), admin, id);
+        env.events().publish(topics, ());
+    }
+
+    /// Emitted when an address is no longer exempt from transfer fees
+    ///
+    /// - topics - `["fee_exempt_removed", admin: Address, id: Address]`
+    /// - data - `()`
+    pub fn fee_exempt_removed(env: &Env, admin: Address, id: Address) {
+        let topics = (Symbol::new(env, "fee_exempt_removed"), admin, id);
+        env.events().publish(topics, ());
+    }
+
+    /// Emitted alongside the `transfer` event when a transfer is flagged as large
+    ///
+    /// - topics - `["large_transfer", from: Address, to: Address]`
+    /// - data - `[amount: i128]`
+    pub fn large_transfer(env: &Env, from: Address, to: Address, amount: i128) {
+        let topics = (Symbol::new(env, "large_transfer"), from, to);
         env.events().publish(topics, amount);
     }
 }
@@ -0,0 +1,26 @@
+//! Fixed-point arithmetic for token amounts, such as fees and exchange rates
+
+use soroban_sdk::{panic_with_error, Env, Error, I256};
+
+/// Calculate `a * b / c`, rounding towards zero, without overflowing on the intermediate
+/// product, such as for `amount * fee_bps / 10000` on large amounts.
+///
+/// The product is computed as an `I256`, so this only panics if the result does not fit
+/// in an `i128`.
+///
+/// # Arguments
+///
+/// - `a` - The first factor
+/// - `b` - The second factor
+/// - `c` - The divisor. Cannot be 0.
+/// - `error` - The error to panic with if `c` is 0 or the result overflows.
+pub fn safe_mul_div(env: &Env, a: i128, b: i128, c: i128, error: impl Into<Error>) -> i128 {
+    if c == 0 {
+        panic_with_error!(env, error);
+    }
+    let product = I256::from_i128(env, a).mul(&I256::from_i128(env, b));
+    match product.div(&I256::from_i128(env, c)).to_i128() {
+        Some(result) => result,
+        None => panic_with_error!(env, error),
+    }
+}
@@ -0,0 +1,26 @@
+//! Minimal oracle interfaces used by `PricedToken` and `ConditionalBurnToken`
+
+use soroban_sdk::{contractclient, Bytes, Env, String};
+
+/// Price Oracle Trait
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    /// Returns the latest USD price of the asset with `symbol`, or None if the oracle
+    /// has no price or the latest price is stale.
+    ///
+    /// # Arguments
+    ///
+    /// - `symbol` - The symbol of the asset being priced.
+    fn get_price(env: Env, symbol: String) -> Option<i128>;
+}
+
+/// Condition Oracle Trait
+#[contractclient(name = "OracleConditionClient")]
+pub trait OracleCondition {
+    /// Returns true if the condition described by `condition_data` is met.
+    ///
+    /// # Arguments
+    ///
+    /// - `condition_data` - An oracle specific encoding of the condition to check.
+    fn check_condition(env: Env, condition_data: Bytes) -> bool;
+}
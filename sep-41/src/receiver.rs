@@ -0,0 +1,20 @@
+//! Interface for contracts notified when they receive tokens via `NotifyingToken`
+
+use soroban_sdk::{contractclient, Address, Bytes, Env};
+
+/// Token Receiver Trait
+#[contractclient(name = "TokenReceiverClient")]
+pub trait TokenReceiver {
+    /// Called by a token after `amount` tokens were transferred from `from` to this
+    /// contract. If this function panics, the transfer is reverted.
+    ///
+    /// Any contract can invoke this function, so receivers must not credit `amount`
+    /// without verifying the tokens were received, e.g. by checking their balance.
+    ///
+    /// # Arguments
+    ///
+    /// - `from` - The address holding the balance of tokens that were sent.
+    /// - `amount` - The amount of tokens that were sent.
+    /// - `data` - Arbitrary data passed by the sender to the receiver.
+    fn on_receive(env: Env, from: Address, amount: i128, data: Bytes);
+}
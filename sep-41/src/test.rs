@@ -0,0 +1,1381 @@
+#![cfg(test)]
+extern crate std;
+
+use crate::{
+    auth,
+    batch::{TokenBatch, TokenOperation},
+    bridge_events::BridgeTokenEvents,
+    client_ext::TokenClientExt,
+    fractional::{self, FractionalAmount},
+    math,
+    testutils::{
+        benchmark_token_ops, check_transfer_from_decrements_allowance,
+        create_token_holding_contract, fuzz_token, run_sep41_conformance_tests,
+        stress_test_allowances, token_snapshot_diff, ConformanceResult, InvariantChecker,
+        MockClock, MockTokenClient, MockTokenWASM, TestScenario, TestTokenBuilder,
+        TokenAddressBook, TokenError, TokenInvariants, TokenSnapshot, SCENARIO_BALANCE,
+    },
+    utils::{self, TokenPair},
+    validate, CappedTokenClient, FeeTokenClient, PausableToken, PermissionedTokenClient,
+    TokenClient, TokenPermissions, TransferReceipt, MAX_DECIMALS,
+};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{
+        Address as _, AuthorizedFunction, AuthorizedInvocation, Events, IssuerFlags, Ledger,
+    },
+    token::StellarAssetClient,
+    vec,
+    xdr::{ScErrorCode, ScErrorType},
+    Address, Bytes, Env, Error, IntoVal, String, Symbol,
+};
+
+fn create_mock_token<'a>(e: &Env, symbol: &str) -> MockTokenClient<'a> {
+    let admin = Address::generate(e);
+    let token = MockTokenClient::new(e, &e.register(MockTokenWASM, ()));
+    token.initialize(
+        &admin,
+        &7,
+        &String::from_str(e, "name"),
+        &String::from_str(e, symbol),
+    );
+    token
+}
+
+#[test]
+fn test_symbol_key() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC");
+
+    let key = utils::symbol_key(&e, &token.address);
+    assert_eq!(key, Symbol::new(&e, "USDC"));
+}
+
+#[test]
+#[should_panic]
+fn test_symbol_key_invalid_symbol() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC.e");
+
+    utils::symbol_key(&e, &token.address);
+}
+
+#[test]
+fn test_is_sep41_token() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC");
+    let not_a_token = Address::generate(&e);
+
+    assert!(utils::is_sep41_token(&e, &token.address));
+    assert!(!utils::is_sep41_token(&e, &not_a_token));
+}
+
+#[test]
+fn test_is_contract_token() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC");
+    let sac = e.register_stellar_asset_contract_v2(Address::generate(&e));
+    let account = Address::generate(&e);
+
+    assert!(utils::is_contract_token(&token.address));
+    assert!(!utils::is_contract_token(&sac.address()));
+    assert!(!utils::is_contract_token(&account));
+}
+
+#[test]
+fn test_token_invariants() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let mut invariants = TokenInvariants::new(&token.address, &[user1.clone(), user2.clone()]);
+    invariants.check_invariants(&e);
+
+    token.mint(&user1, &1000);
+    invariants.mint(1000);
+    invariants.check_invariants(&e);
+
+    token.approve(&user1, &user2, &5000, &100);
+    token.transfer(&user1, &user2, &400);
+    invariants.check_invariants(&e);
+
+    token.burn(&user2, &100);
+    invariants.burn(100);
+    invariants.check_invariants(&e);
+}
+
+#[test]
+#[should_panic(expected = "invariant failed: sum of balances 1000 exceeds total supply 999")]
+fn test_token_invariants_supply_exceeded() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user1 = Address::generate(&e);
+    let mut invariants = TokenInvariants::new(&token.address, core::slice::from_ref(&user1));
+
+    token.mint(&user1, &1000);
+    invariants.mint(999);
+    invariants.check_invariants(&e);
+}
+
+#[test]
+fn test_fuzz_token() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let accounts = [
+        Address::generate(&e),
+        Address::generate(&e),
+        Address::generate(&e),
+    ];
+    token.mint(&accounts[0], &1000);
+
+    fuzz_token(&e, &token.address, &accounts, 100);
+}
+
+#[test]
+fn test_bridge_transfer_event() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC");
+    let from = Address::from_str(
+        &e,
+        "GAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQDZ7H",
+    );
+    let to = Address::from_str(
+        &e,
+        "CABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAFNSZ",
+    );
+
+    e.as_contract(&token.address, || {
+        BridgeTokenEvents::transfer(&e, from.clone(), to.clone(), 0x0102);
+    });
+
+    let mut expected_data = [0u8; 96];
+    expected_data[..32].copy_from_slice(&[1; 32]);
+    expected_data[32..64].copy_from_slice(&[2; 32]);
+    expected_data[94] = 0x01;
+    expected_data[95] = 0x02;
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                token.address.clone(),
+                (symbol_short!("transfer"), from, to).into_val(&e),
+                Bytes::from_array(&e, &expected_data).into_val(&e)
+            )
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "bridge events cannot encode a negative amount")]
+fn test_bridge_transfer_event_negative_amount() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC");
+
+    e.as_contract(&token.address, || {
+        BridgeTokenEvents::transfer(&e, Address::generate(&e), Address::generate(&e), -1);
+    });
+}
+
+#[test]
+fn test_is_authorized() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+
+    // tokens without authorization flags authorize everyone
+    let token = create_mock_token(&e, "USDC");
+    assert!(utils::is_authorized(&e, &token.address, &user));
+
+    let sac = e.register_stellar_asset_contract_v2(admin.clone());
+    sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let sac_client = StellarAssetClient::new(&e, &sac.address());
+    assert!(utils::is_authorized(&e, &sac.address(), &user));
+
+    sac_client.set_authorized(&user, &false);
+    assert!(!utils::is_authorized(&e, &sac.address(), &user));
+}
+
+#[contract]
+struct Pool;
+
+#[contractimpl]
+impl Pool {
+    pub fn approve_pair(e: Env, pair: TokenPair, owner: Address, amount_a: i128, amount_b: i128) {
+        owner.require_auth();
+        utils::create_pair_allowance(&e, &pair, owner, amount_a, amount_b, 1000);
+    }
+}
+
+#[test]
+fn test_create_pair_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token_a = create_mock_token(&e, "USDC");
+    let token_b = create_mock_token(&e, "XLM");
+    let pool = e.register(Pool {}, ());
+    let pool_client = PoolClient::new(&e, &pool);
+    let user = Address::generate(&e);
+    let pair = TokenPair {
+        token_a: token_a.address.clone(),
+        token_b: token_b.address.clone(),
+    };
+
+    pool_client.approve_pair(&pair, &user, &100, &200);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    pool.clone(),
+                    Symbol::new(&e, "approve_pair"),
+                    (pair.clone(), &user, 100_i128, 200_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![
+                    AuthorizedInvocation {
+                        function: AuthorizedFunction::Contract((
+                            token_a.address.clone(),
+                            symbol_short!("approve"),
+                            (&user, &pool, 100_i128, 1000_u32).into_val(&e),
+                        )),
+                        sub_invocations: std::vec![]
+                    },
+                    AuthorizedInvocation {
+                        function: AuthorizedFunction::Contract((
+                            token_b.address.clone(),
+                            symbol_short!("approve"),
+                            (&user, &pool, 200_i128, 1000_u32).into_val(&e),
+                        )),
+                        sub_invocations: std::vec![]
+                    }
+                ]
+            }
+        )]
+    );
+    assert_eq!(token_a.allowance(&user, &pool), 100);
+    assert_eq!(token_b.allowance(&user, &pool), 200);
+}
+
+#[test]
+fn test_test_token_builder() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let bad_addr = Address::generate(&e);
+
+    let (address, token) = TestTokenBuilder::new(&e)
+        .with_admin(admin.clone())
+        .with_decimals(6)
+        .with_cap(10_000_000_000_000)
+        .with_fee_bps(50)
+        .with_blacklist(bad_addr.clone())
+        .build();
+    let mock = MockTokenClient::new(&e, &address);
+    assert_eq!(token.decimals(), 6);
+    assert_eq!(
+        CappedTokenClient::new(&e, &address).cap(),
+        10_000_000_000_000
+    );
+    assert_eq!(FeeTokenClient::new(&e, &address).transfer_fee_bps(), 50);
+    assert_eq!(
+        FeeTokenClient::new(&e, &address).fee_recipient(),
+        Some(admin.clone())
+    );
+    assert!(!mock.authorized(&bad_addr));
+
+    mock.mint(&user1, &10000);
+    token.transfer(&user1, &user2, &10000);
+    assert_eq!(token.balance(&user2), 9950);
+    assert_eq!(token.balance(&admin), 50);
+
+    let result = token.try_transfer(&user2, &bad_addr, &100);
+    assert_eq!(result, Err(Ok(TokenError::UnauthorizedError.into())));
+}
+
+#[test]
+fn test_token_snapshot_diff() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let user3 = Address::generate(&e);
+    let accounts = [user1.clone(), user2.clone(), user3.clone()];
+    token.mint(&user1, &1000);
+
+    let before = TokenSnapshot::capture(&e, &token.address, &accounts);
+    token.transfer(&user1, &user2, &300);
+    token.burn(&user2, &100);
+    let after = TokenSnapshot::capture(&e, &token.address, &accounts);
+
+    assert_eq!(
+        token_snapshot_diff(&before, &after),
+        std::vec![(user1, 1000, 700), (user2, 0, 200)]
+    );
+    assert!(token_snapshot_diff(&after, &after).is_empty());
+}
+
+#[test]
+fn test_approve_max() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user = Address::generate(&e);
+    let spender = Address::generate(&e);
+
+    utils::approve_max(&e, &token.address, &user, &spender, 1000);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("approve"),
+                    (&user, &spender, i128::MAX, 1000_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.allowance(&user, &spender), i128::MAX);
+}
+
+#[test]
+fn test_revoke_approval() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user = Address::generate(&e);
+    let spender = Address::generate(&e);
+    e.ledger().set_sequence_number(100);
+
+    token.approve(&user, &spender, &500, &1000);
+    utils::revoke_approval(&e, &token.address, &user, &spender);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            user.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("approve"),
+                    (&user, &spender, 0_i128, 0_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.allowance(&user, &spender), 0);
+}
+
+#[test]
+fn test_token_client_ext() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    token.mint(&user1, &1000);
+    let client = TokenClient::new(&e, &token.address);
+
+    assert_eq!(client.transfer_checked(&user1, &user2, &400), Ok(()));
+    assert_eq!(
+        client.transfer_checked(&user1, &user2, &700),
+        Err(TokenError::BalanceError.into())
+    );
+    assert_eq!(client.approve_checked(&user2, &user1, &300, &1000), Ok(()));
+    assert_eq!(
+        client.transfer_from_checked(&user1, &user2, &user1, &301),
+        Err(TokenError::AllowanceError.into())
+    );
+    assert_eq!(client.burn_from_checked(&user1, &user2, &300), Ok(()));
+    assert_eq!(client.burn_checked(&user1, &600), Ok(()));
+    assert_eq!(
+        client.burn_checked(&user1, &-1),
+        Err(Error::from_type_and_code(
+            ScErrorType::Context,
+            ScErrorCode::InvalidAction
+        ))
+    );
+    assert_eq!(token.balance(&user1), 0);
+    assert_eq!(token.balance(&user2), 100);
+}
+
+#[test]
+fn test_supply_cap() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    assert_eq!(utils::supply_cap(&e, &token.address), None);
+
+    CappedTokenClient::new(&e, &token.address).set_cap(&1_000_000);
+    assert_eq!(utils::supply_cap(&e, &token.address), Some(1_000_000));
+
+    let sac = e.register_stellar_asset_contract_v2(Address::generate(&e));
+    assert_eq!(utils::supply_cap(&e, &sac.address()), None);
+}
+
+#[test]
+fn test_lazy_total_supply() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let scenario = TestScenario::simple(&e, &Address::generate(&e));
+    let token = &scenario.token.address;
+
+    assert_eq!(
+        utils::lazy_total_supply(&e, token, 3),
+        Some(3 * SCENARIO_BALANCE)
+    );
+    scenario.token.burn(&scenario.user1, &SCENARIO_BALANCE);
+    assert_eq!(
+        utils::lazy_total_supply(&e, token, 2),
+        Some(2 * SCENARIO_BALANCE)
+    );
+    assert_eq!(utils::lazy_total_supply(&e, token, 1), None);
+
+    let sac = e.register_stellar_asset_contract_v2(Address::generate(&e));
+    assert_eq!(utils::lazy_total_supply(&e, &sac.address(), 100), None);
+}
+
+#[test]
+fn test_token_address_checksum() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC");
+
+    let mut preimage = token.address.to_string().to_bytes();
+    preimage.append(&Bytes::from_slice(&e, b"nameUSDC"));
+    preimage.extend_from_array(&7u32.to_be_bytes());
+    let hash = e.crypto().sha256(&preimage).to_array();
+    let checksum = utils::token_address_checksum(&e, &token.address);
+    assert_eq!(checksum.to_array(), [hash[0], hash[1], hash[2], hash[3]]);
+
+    // tokens with identical metadata have different checksums
+    let other = create_mock_token(&e, "USDC");
+    assert_ne!(utils::token_address_checksum(&e, &other.address), checksum);
+}
+
+#[test]
+fn test_test_scenario() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+
+    let scenario = TestScenario::simple(&e, &admin);
+    for user in [&scenario.user1, &scenario.user2, &scenario.user3] {
+        assert_eq!(scenario.token.balance(user), SCENARIO_BALANCE);
+    }
+    assert_eq!(scenario.token.balance(&scenario.spender), 0);
+
+    let scenario = TestScenario::with_allowance(&e, &admin, 500);
+    assert_eq!(
+        scenario.token.allowance(&scenario.user1, &scenario.spender),
+        500
+    );
+    scenario
+        .token
+        .transfer_from(&scenario.spender, &scenario.user1, &scenario.user2, &500);
+    assert_eq!(scenario.token.balance(&scenario.user2), 1500);
+
+    let scenario = TestScenario::with_frozen_account(&e, &admin);
+    let result = scenario
+        .token
+        .try_transfer(&scenario.user3, &scenario.user1, &100);
+    assert_eq!(result, Err(Ok(TokenError::UnauthorizedError.into())));
+}
+
+#[test]
+fn test_benchmark_token_ops() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC");
+
+    let benchmark = benchmark_token_ops(&e, &token.address);
+    assert!(benchmark.balance_cpu_instructions > 0);
+    // mutating operations write to storage, so cost more than a balance query
+    for cpu_instructions in [
+        benchmark.mint_cpu_instructions,
+        benchmark.transfer_cpu_instructions,
+        benchmark.approve_cpu_instructions,
+        benchmark.transfer_from_cpu_instructions,
+        benchmark.burn_cpu_instructions,
+        benchmark.burn_from_cpu_instructions,
+    ] {
+        assert!(cpu_instructions > benchmark.balance_cpu_instructions);
+    }
+}
+
+#[test]
+fn test_stress_test_allowances() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC");
+    let from = Address::generate(&e);
+
+    let stress = stress_test_allowances(&e, &token.address, &from, 100);
+    assert!(stress.first_allowance_cpu_instructions > 0);
+    // each allowance is stored under its own key, so the last query costs about as much
+    // as the first
+    assert!(
+        stress.last_allowance_cpu_instructions <= stress.first_allowance_cpu_instructions * 11 / 10
+    );
+}
+
+#[contract]
+struct Router;
+
+#[contractimpl]
+impl Router {
+    pub fn transfer_twice(e: Env, token: Address, from: Address, to: (Address, Address)) {
+        from.require_auth();
+        let client = TokenClient::new(&e, &token);
+        client.transfer(&from, &to.0, &100);
+        client.transfer(&from, &to.1, &100);
+    }
+}
+
+#[test]
+fn test_transfers_in_one_transaction() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let router = RouterClient::new(&e, &e.register(Router {}, ()));
+    let a = Address::generate(&e);
+    let b = Address::generate(&e);
+    let c = Address::generate(&e);
+    let to = (b.clone(), c.clone());
+
+    // both transfers draw from the same balance, and the transaction reverts as a whole
+    token.mint(&a, &150);
+    let result = router.try_transfer_twice(&token.address, &a, &to);
+    assert_eq!(result, Err(Ok(TokenError::BalanceError.into())));
+    assert_eq!(token.balance(&a), 150);
+    assert_eq!(token.balance(&b), 0);
+
+    token.mint(&a, &50);
+    router.transfer_twice(&token.address, &a, &to);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            a.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    router.address.clone(),
+                    Symbol::new(&e, "transfer_twice"),
+                    (&token.address, &a, to.clone()).into_val(&e),
+                )),
+                sub_invocations: std::vec![
+                    AuthorizedInvocation {
+                        function: AuthorizedFunction::Contract((
+                            token.address.clone(),
+                            symbol_short!("transfer"),
+                            (&a, &b, 100_i128).into_val(&e),
+                        )),
+                        sub_invocations: std::vec![]
+                    },
+                    AuthorizedInvocation {
+                        function: AuthorizedFunction::Contract((
+                            token.address.clone(),
+                            symbol_short!("transfer"),
+                            (&a, &c, 100_i128).into_val(&e),
+                        )),
+                        sub_invocations: std::vec![]
+                    }
+                ]
+            }
+        )]
+    );
+    assert_eq!(token.balance(&a), 0);
+    assert_eq!(token.balance(&b), 100);
+    assert_eq!(token.balance(&c), 100);
+}
+
+#[test]
+fn test_validate_not_zero_address() {
+    let e = Env::default();
+    let token = create_mock_token(&e, "USDC");
+
+    let error = Error::from_contract_error(1);
+    validate::validate_not_zero_address(&e, &Address::generate(&e), error);
+    validate::validate_not_zero_address(&e, &token.address, error);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_validate_not_zero_address_account() {
+    let e = Env::default();
+    let address = Address::from_str(&e, validate::ZERO_ACCOUNT_ADDRESS);
+
+    validate::validate_not_zero_address(&e, &address, Error::from_contract_error(1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_validate_not_zero_address_contract() {
+    let e = Env::default();
+    let address = Address::from_str(&e, validate::ZERO_CONTRACT_ADDRESS);
+
+    validate::validate_not_zero_address(&e, &address, Error::from_contract_error(1));
+}
+
+#[test]
+fn test_aggregate_receipts() {
+    let e = Env::default();
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let user3 = Address::generate(&e);
+    let receipt = |gross_amount: i128, fee: i128| TransferReceipt {
+        gross_amount,
+        net_amount: gross_amount - fee,
+        fee,
+        from_balance_after: 0,
+        to_balance_after: 0,
+    };
+
+    let deltas = utils::aggregate_receipts(
+        &e,
+        &[
+            (user1.clone(), user2.clone(), receipt(100, 1)),
+            (user2.clone(), user3.clone(), receipt(50, 0)),
+            (user3.clone(), user1.clone(), receipt(20, 2)),
+        ],
+    );
+    assert_eq!(deltas.len(), 3);
+    assert_eq!(deltas.get(user1), Some(-82));
+    assert_eq!(deltas.get(user2), Some(49));
+    assert_eq!(deltas.get(user3), Some(30));
+    assert_eq!(deltas.values().iter().sum::<i128>(), -3);
+    assert!(utils::aggregate_receipts(&e, &[]).is_empty());
+}
+
+#[test]
+fn test_fractional_amount() {
+    let half = FractionalAmount(5, 1);
+    assert_eq!(half, FractionalAmount(500_000_000_000_000_000, 18));
+    assert!(half < FractionalAmount(51, 2));
+    assert!(half > FractionalAmount(-1, 0));
+    assert_eq!(half.partial_cmp(&FractionalAmount(i128::MAX, 0)), None);
+
+    let sum = half.checked_add(&FractionalAmount(25, 2)).unwrap();
+    assert_eq!((sum.0, sum.1), (75, 2));
+    let diff = half.checked_sub(&FractionalAmount(1, 0)).unwrap();
+    assert_eq!((diff.0, diff.1), (-5, 1));
+    assert!(FractionalAmount(i128::MAX, 0)
+        .checked_add(&FractionalAmount(1, 0))
+        .is_none());
+    assert!(FractionalAmount(15, 1).rescale(0).is_none());
+    assert!(FractionalAmount(1, 0).rescale(MAX_DECIMALS).is_some());
+    assert!(FractionalAmount(1, 0).rescale(MAX_DECIMALS + 1).is_none());
+
+    assert_eq!(std::format!("{}", half), "0.5");
+    assert_eq!(std::format!("{}", FractionalAmount(-1_250, 3)), "-1.25");
+    assert_eq!(std::format!("{}", FractionalAmount(7, 0)), "7");
+    assert_eq!(std::format!("{}", FractionalAmount(3_000, 3)), "3");
+    assert_eq!(
+        std::format!("{}", FractionalAmount(1_000_000_000_000_000_001, 18)),
+        "1.000000000000000001"
+    );
+    // places beyond 18 are truncated
+    assert_eq!(
+        std::format!("{}", FractionalAmount(1_234_567_890_123_456_789_999, 21)),
+        "1.234567890123456789"
+    );
+    // amounts too small to show within 18 places display as 0
+    assert_eq!(
+        std::format!("{}", FractionalAmount(10i128.pow(38), 60)),
+        "0"
+    );
+    assert_eq!(
+        std::format!("{}", FractionalAmount(10i128.pow(38), 57)),
+        "0"
+    );
+    assert_eq!(
+        std::format!("{}", FractionalAmount(10i128.pow(38), 56)),
+        "0.000000000000000001"
+    );
+}
+
+#[test]
+fn test_fractional_balance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (address, _) = TestTokenBuilder::new(&e).with_decimals(18).build();
+    let token = MockTokenClient::new(&e, &address);
+    let user = Address::generate(&e);
+
+    validate::require_fractional_decimals(&e, token.decimals(), Error::from_contract_error(1));
+    token.mint(&user, &250_000_000_000_000_000);
+    let balance = fractional::fractional_balance(&e, &address, &user);
+    assert_eq!(
+        balance,
+        FractionalAmount(1, 0)
+            .checked_sub(&FractionalAmount(75, 2))
+            .unwrap()
+    );
+    assert_eq!(std::format!("{}", balance), "0.25");
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_require_fractional_decimals() {
+    let e = Env::default();
+
+    validate::require_fractional_decimals(&e, 7, Error::from_contract_error(1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_validate_decimals() {
+    let e = Env::default();
+
+    validate::validate_decimals(&e, MAX_DECIMALS, Error::from_contract_error(1));
+    validate::validate_decimals(&e, MAX_DECIMALS + 1, Error::from_contract_error(1));
+}
+
+#[test]
+fn test_clawback_from_contract() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let scenario = TestScenario::simple(&e, &Address::generate(&e));
+    let token = scenario.token;
+    let holder = create_token_holding_contract(&e);
+
+    token.transfer(&scenario.user1, &holder, &600);
+    assert_eq!(token.balance(&holder), 600);
+
+    StellarAssetClient::new(&e, &token.address).clawback(&holder, &250);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            scenario.admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    symbol_short!("clawback"),
+                    (holder.clone(), 250_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+    assert_eq!(token.balance(&holder), 350);
+    assert_eq!(token.total_supply(), 3 * SCENARIO_BALANCE - 250);
+}
+
+#[test]
+fn test_refund_transfer() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let scenario = TestScenario::with_frozen_account(&e, &Address::generate(&e));
+    let token = scenario.token;
+
+    assert!(!utils::refund_transfer(
+        &e,
+        &token.address,
+        &scenario.user1,
+        &scenario.user3,
+        100
+    ));
+    assert_eq!(token.balance(&scenario.user1), SCENARIO_BALANCE);
+    assert_eq!(token.balance(&scenario.user3), SCENARIO_BALANCE);
+
+    assert!(utils::refund_transfer(
+        &e,
+        &token.address,
+        &scenario.user1,
+        &scenario.user2,
+        100
+    ));
+    assert_eq!(token.balance(&scenario.user1), SCENARIO_BALANCE - 100);
+    assert_eq!(token.balance(&scenario.user2), SCENARIO_BALANCE + 100);
+
+    // a recipient that cannot receive is ineligible even though it is authorized
+    PermissionedTokenClient::new(&e, &token.address).set_permissions(
+        &scenario.user2,
+        &TokenPermissions {
+            can_send: true,
+            can_receive: false,
+            can_burn: true,
+            can_approve: true,
+        },
+    );
+    assert!(!utils::refund_transfer(
+        &e,
+        &token.address,
+        &scenario.user1,
+        &scenario.user2,
+        100
+    ));
+    assert_eq!(token.balance(&scenario.user1), SCENARIO_BALANCE - 100);
+    assert_eq!(token.balance(&scenario.user2), SCENARIO_BALANCE + 100);
+}
+
+/// The size budget of the mock token wasm. Raise it consciously when adding code.
+const MAX_WASM_SIZE: usize = 67 * 1024;
+
+#[test]
+fn test_mock_token_wasm_size() {
+    assert!(
+        MockTokenWASM.len() < MAX_WASM_SIZE,
+        "mock token wasm is {} bytes, exceeding the budget of {} bytes",
+        MockTokenWASM.len(),
+        MAX_WASM_SIZE
+    );
+}
+
+#[test]
+fn test_transfer_from_decrements_allowance() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let scenario = TestScenario::simple(&e, &Address::generate(&e));
+
+    assert_eq!(
+        check_transfer_from_decrements_allowance(
+            &e,
+            &scenario.token.address,
+            &scenario.user1,
+            &scenario.spender,
+            &scenario.user2,
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        scenario.token.balance(&scenario.user2),
+        SCENARIO_BALANCE + 40
+    );
+}
+
+#[test]
+fn test_transfer_from_decrements_allowance_with_fee() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let scenario = TestScenario::simple(&e, &Address::generate(&e));
+    FeeTokenClient::new(&e, &scenario.token.address)
+        .set_transfer_fee(&1000, &Address::generate(&e));
+
+    assert_eq!(
+        check_transfer_from_decrements_allowance(
+            &e,
+            &scenario.token.address,
+            &scenario.user1,
+            &scenario.spender,
+            &scenario.user2,
+        ),
+        Ok(())
+    );
+    assert_eq!(
+        scenario.token.balance(&scenario.user2),
+        SCENARIO_BALANCE + 36
+    );
+}
+
+#[test]
+fn test_token_batch() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let scenario = TestScenario::simple(&e, &Address::generate(&e));
+    let token = scenario.token;
+
+    let mut batch = TokenBatch::new(&e);
+    batch
+        .add_transfer(&scenario.user1, &scenario.user2, 400)
+        .add_burn(&scenario.user1, SCENARIO_BALANCE)
+        .add_approve(&scenario.user2, &scenario.spender, 300, 1000);
+    assert_eq!(batch.operations.len(), 3);
+    assert_eq!(
+        batch.operations.get_unchecked(1),
+        TokenOperation::Burn(scenario.user1.clone(), SCENARIO_BALANCE)
+    );
+    // the batch does not invoke the token until it is simulated
+    assert_eq!(token.balance(&scenario.user2), SCENARIO_BALANCE);
+
+    assert_eq!(
+        batch.simulate(&e, &token.address),
+        vec![&e, Ok(()), Err(TokenError::BalanceError.into()), Ok(())]
+    );
+    assert_eq!(token.balance(&scenario.user1), SCENARIO_BALANCE - 400);
+    assert_eq!(token.balance(&scenario.user2), SCENARIO_BALANCE + 400);
+    assert_eq!(token.allowance(&scenario.user2, &scenario.spender), 300);
+}
+
+#[test]
+fn test_run_sep41_conformance_tests() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let (token, _) = TestTokenBuilder::new(&e).with_admin(admin.clone()).build();
+
+    let results = run_sep41_conformance_tests(
+        &e,
+        token,
+        admin,
+        Address::generate(&e),
+        Address::generate(&e),
+    );
+    assert_eq!(results.len(), 11);
+    for result in results.iter() {
+        assert!(result.passed(), "{:?}", result);
+    }
+}
+
+#[test]
+fn test_run_sep41_conformance_tests_wrong_admin() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let (token, _) = TestTokenBuilder::new(&e).build();
+
+    let results = run_sep41_conformance_tests(
+        &e,
+        token,
+        Address::generate(&e),
+        Address::generate(&e),
+        Address::generate(&e),
+    );
+    assert_eq!(
+        results,
+        std::vec![ConformanceResult {
+            name: "setup",
+            result: Err(std::string::String::from(
+                "mint was not authorized by admin"
+            )),
+        }]
+    );
+}
+
+#[test]
+fn test_validate_string_utf8() {
+    let e = Env::default();
+
+    assert!(validate::validate_string_utf8(&String::from_str(
+        &e, "USD Coin"
+    )));
+    assert!(validate::validate_string_utf8(&String::from_str(
+        &e,
+        "€uro 🪙"
+    )));
+    assert!(validate::validate_string_utf8(&String::from_str(&e, "")));
+    // control characters
+    assert!(!validate::validate_string_utf8(&String::from_str(
+        &e,
+        "USD\tCoin"
+    )));
+    assert!(!validate::validate_string_utf8(&String::from_bytes(
+        &e,
+        &[0x00]
+    )));
+    // invalid continuation bytes, overlong encodings, and surrogates
+    assert!(!validate::validate_string_utf8(&String::from_bytes(
+        &e,
+        &[0xE2, 0x82]
+    )));
+    assert!(!validate::validate_string_utf8(&String::from_bytes(
+        &e,
+        &[0xC0, 0xAF]
+    )));
+    assert!(!validate::validate_string_utf8(&String::from_bytes(
+        &e,
+        &[0xE0, 0x80, 0xAF]
+    )));
+    assert!(!validate::validate_string_utf8(&String::from_bytes(
+        &e,
+        &[0xED, 0xA0, 0x80]
+    )));
+    assert!(!validate::validate_string_utf8(&String::from_bytes(
+        &e,
+        &[0xFF]
+    )));
+}
+
+#[contract]
+struct Payer;
+
+#[contractimpl]
+impl Payer {
+    pub fn pay(e: Env, from: Address, to: Address, amount: i128, _memo: u64) {
+        auth::require_transfer_auth(&e, &from, &to, amount);
+    }
+
+    pub fn allow(e: Env, from: Address, spender: Address, amount: i128) {
+        auth::require_approve_auth(&e, &from, &spender, amount, 1000);
+    }
+
+    pub fn destroy(e: Env, from: Address, amount: i128) {
+        auth::require_burn_auth(&e, &from, amount);
+    }
+
+    pub fn relay(e: Env, from: Address, nonce: u64) {
+        from.require_auth();
+        if nonce != auth::read_nonce(&e, &from) {
+            panic!("invalid nonce");
+        }
+        auth::increment_nonce(&e, &from);
+    }
+
+    pub fn nonce(e: Env, from: Address) -> u64 {
+        auth::read_nonce(&e, &from)
+    }
+}
+
+#[test]
+fn test_auth_helpers() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let payer = PayerClient::new(&e, &e.register(Payer {}, ()));
+    let from = Address::generate(&e);
+    let to = Address::generate(&e);
+
+    payer.pay(&from, &to, &100, &7);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            from.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    payer.address.clone(),
+                    Symbol::new(&e, "pay"),
+                    (from.clone(), to.clone(), 100_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+
+    payer.allow(&from, &to, &100);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            from.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    payer.address.clone(),
+                    Symbol::new(&e, "allow"),
+                    (from.clone(), to.clone(), 100_i128, 1000_u32).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+
+    payer.destroy(&from, &100);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            from.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    payer.address.clone(),
+                    Symbol::new(&e, "destroy"),
+                    (from.clone(), 100_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![]
+            }
+        )]
+    );
+}
+
+#[test]
+fn test_allowance_or_zero() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let client = TokenClient::new(&e, &token.address);
+
+    assert_eq!(client.allowance_or_zero(&user1, &user2), 0);
+    token.approve(&user1, &user2, &300, &1000);
+    assert_eq!(client.allowance_or_zero(&user1, &user2), 300);
+
+    // a contract without an allowance function fails the query
+    let not_token = TokenClient::new(&e, &create_token_holding_contract(&e));
+    assert_eq!(not_token.allowance_or_zero(&user1, &user2), 0);
+}
+
+#[test]
+fn test_transfer_fee_estimate() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let client = TokenClient::new(&e, &token.address);
+
+    FeeTokenClient::new(&e, &token.address).set_transfer_fee(&100, &Address::generate(&e));
+    assert_eq!(client.transfer_fee_estimate(&user1, &user2, &1000), 10);
+
+    // tokens without fees charge nothing
+    let sac = e.register_stellar_asset_contract_v2(Address::generate(&e));
+    let sac_client = TokenClient::new(&e, &sac.address());
+    assert_eq!(sac_client.transfer_fee_estimate(&user1, &user2, &1000), 0);
+}
+
+#[test]
+fn test_balance_checked() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user = Address::generate(&e);
+    token.mint(&user, &1000);
+
+    assert_eq!(
+        TokenClient::new(&e, &token.address).balance_checked(&user),
+        Some(1000)
+    );
+
+    // contracts that are not tokens return None rather than panicking
+    let paused = e.register(PausedToken, ());
+    assert_eq!(TokenClient::new(&e, &paused).balance_checked(&user), None);
+}
+
+#[contract]
+struct PausedToken;
+
+#[contractimpl]
+impl PausableToken for PausedToken {
+    fn is_paused(_e: Env) -> bool {
+        true
+    }
+}
+
+#[test]
+fn test_is_paused() {
+    let e = Env::default();
+    let paused = e.register(PausedToken, ());
+    assert!(TokenClient::new(&e, &paused).is_paused());
+
+    // tokens without the extension are never paused
+    let token = create_mock_token(&e, "USDC");
+    assert!(!TokenClient::new(&e, &token.address).is_paused());
+    let sac = e.register_stellar_asset_contract_v2(Address::generate(&e));
+    assert!(!TokenClient::new(&e, &sac.address()).is_paused());
+}
+
+#[test]
+fn test_clawback_checked() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let token = create_mock_token(&e, "USDC");
+    let user = Address::generate(&e);
+    token.mint(&user, &1000);
+    let client = TokenClient::new(&e, &token.address);
+
+    assert_eq!(client.clawback_checked(&user, &400), Ok(()));
+    assert_eq!(
+        client.clawback_checked(&user, &700),
+        Err(TokenError::BalanceError.into())
+    );
+    assert_eq!(token.balance(&user), 600);
+}
+
+#[test]
+fn test_mock_clock() {
+    let e = Env::default();
+    e.ledger().set_sequence_number(100);
+    e.ledger().set_timestamp(10_000);
+
+    let clock = MockClock::new(&e);
+    assert_eq!(clock.current_ledger(), 100);
+    assert_eq!(clock.now_as_timestamp(), 10_000);
+
+    clock.advance(10);
+    assert_eq!(clock.current_ledger(), 110);
+    assert_eq!(e.ledger().sequence(), 110);
+    assert_eq!(clock.now_as_timestamp(), 10_050);
+
+    clock.set(105);
+    assert_eq!(clock.current_ledger(), 105);
+    assert_eq!(clock.now_as_timestamp(), 10_025);
+}
+
+#[test]
+fn test_token_address_book() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let book = TokenAddressBook::new(&e);
+
+    // addresses are derived from their names, not from the addresses generated before
+    Address::generate(&e);
+    let other_env = Env::default();
+    let other_book = TokenAddressBook::new(&other_env);
+    assert_eq!(book.alice.to_string(), other_book.alice.to_string());
+    assert_eq!(book.pool.to_string(), other_book.pool.to_string());
+    assert_ne!(book.alice, book.bob);
+
+    let token = MockTokenClient::new(&e, &e.register(MockTokenWASM, ()));
+    token.initialize(
+        &book.admin,
+        &7,
+        &String::from_str(&e, "name"),
+        &String::from_str(&e, "symbol"),
+    );
+    token.mint(&book.alice, &1000);
+    token.transfer(&book.alice, &book.bob, &400);
+    assert_eq!(token.balance(&book.alice), 600);
+    assert_eq!(token.balance(&book.bob), 400);
+}
+
+#[test]
+fn test_token_lifecycle() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let spender = Address::generate(&e);
+
+    let token = MockTokenClient::new(&e, &e.register(MockTokenWASM, ()));
+    token.initialize(
+        &admin,
+        &7,
+        &String::from_str(&e, "name"),
+        &String::from_str(&e, "symbol"),
+    );
+    let client = TokenClient::new(&e, &token.address);
+    let address = token.address.clone();
+
+    token.mint(&user1, &1000);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                address.clone(),
+                (symbol_short!("mint"), admin.clone(), user1.clone()).into_val(&e),
+                1000_i128.into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(token.stats().total_supply, 1000);
+
+    client.transfer(&user1, &user2, &200);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                address.clone(),
+                (symbol_short!("transfer"), user1.clone(), user2.clone()).into_val(&e),
+                200_i128.into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(client.balance(&user1), 800);
+    assert_eq!(client.balance(&user2), 200);
+
+    client.approve(&user1, &spender, &500, &1000);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                address.clone(),
+                (symbol_short!("approve"), user1.clone(), spender.clone()).into_val(&e),
+                (500_i128, 1000_u32).into_val(&e)
+            )
+        ]
+    );
+
+    client.transfer_from(&spender, &user1, &user2, &300);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                address.clone(),
+                (symbol_short!("transfer"), user1.clone(), user2.clone()).into_val(&e),
+                300_i128.into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(client.allowance(&user1, &spender), 200);
+    assert_eq!(client.balance(&user2), 500);
+
+    client.burn(&user2, &100);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                address.clone(),
+                (symbol_short!("burn"), user2.clone()).into_val(&e),
+                100_i128.into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(token.stats().total_supply, 900);
+
+    client.burn_from(&spender, &user1, &200);
+    assert_eq!(
+        e.events().all(),
+        vec![
+            &e,
+            (
+                address.clone(),
+                (symbol_short!("burn"), user1.clone()).into_val(&e),
+                200_i128.into_val(&e)
+            )
+        ]
+    );
+    assert_eq!(client.allowance(&user1, &spender), 0);
+    assert_eq!(client.balance(&user1), 300);
+    assert_eq!(token.stats().total_supply, 700);
+}
+
+#[test]
+fn test_safe_mul_div() {
+    let e = Env::default();
+    let error = Error::from_contract_error(1);
+
+    assert_eq!(math::safe_mul_div(&e, 1000, 25, 10_000, error), 2);
+    assert_eq!(math::safe_mul_div(&e, -1000, 25, 10_000, error), -2);
+    // the intermediate product exceeds i128::MAX
+    assert_eq!(
+        math::safe_mul_div(&e, i128::MAX, 10_000, 10_000, error),
+        i128::MAX
+    );
+    assert_eq!(
+        math::safe_mul_div(&e, i128::MAX / 3, 9, 10, error),
+        i128::MAX / 3 / 10 * 9 + (i128::MAX / 3 % 10) * 9 / 10
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_safe_mul_div_overflow() {
+    let e = Env::default();
+    math::safe_mul_div(&e, i128::MAX, 2, 1, Error::from_contract_error(1));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #1)")]
+fn test_safe_mul_div_by_zero() {
+    let e = Env::default();
+    math::safe_mul_div(&e, 1, 1, 0, Error::from_contract_error(1));
+}
+
+#[test]
+fn test_nonce() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let payer = PayerClient::new(&e, &e.register(Payer {}, ()));
+    let from = Address::generate(&e);
+    let other = Address::generate(&e);
+
+    assert_eq!(payer.nonce(&from), 0);
+    payer.relay(&from, &0);
+    assert_eq!(payer.nonce(&from), 1);
+    assert_eq!(payer.nonce(&other), 0);
+
+    // a signed operation cannot be replayed
+    assert!(payer.try_relay(&from, &0).is_err());
+    payer.relay(&from, &1);
+    assert_eq!(payer.nonce(&from), 2);
+
+    let token = create_mock_token(&e, "USDC");
+    assert_eq!(token.nonce(&from), 0);
+}
@@ -0,0 +1,47 @@
+use soroban_sdk::{
+    xdr::{ContractId, Hash, ScAddress},
+    Address, Bytes, Env, TryFromVal,
+};
+
+/// Named addresses for tests, such that tests read in terms of their participants rather
+/// than generated addresses.
+///
+/// Each address is derived from its name, so the same name always refers to the same
+/// address, regardless of how many addresses a test generates.
+pub struct TokenAddressBook {
+    pub alice: Address,
+    pub bob: Address,
+    pub carol: Address,
+    pub dave: Address,
+    pub eve: Address,
+    pub mallory: Address,
+    pub admin: Address,
+    pub faucet: Address,
+    pub treasury: Address,
+    pub pool: Address,
+}
+
+impl TokenAddressBook {
+    pub fn new(env: &Env) -> Self {
+        TokenAddressBook {
+            alice: named_address(env, "alice"),
+            bob: named_address(env, "bob"),
+            carol: named_address(env, "carol"),
+            dave: named_address(env, "dave"),
+            eve: named_address(env, "eve"),
+            mallory: named_address(env, "mallory"),
+            admin: named_address(env, "admin"),
+            faucet: named_address(env, "faucet"),
+            treasury: named_address(env, "treasury"),
+            pool: named_address(env, "pool"),
+        }
+    }
+}
+
+/// Derive the contract address whose id is the SHA-256 hash of `name`
+pub fn named_address(env: &Env, name: &str) -> Address {
+    let hash = env
+        .crypto()
+        .sha256(&Bytes::from_slice(env, name.as_bytes()));
+    Address::try_from_val(env, &ScAddress::Contract(ContractId(Hash(hash.to_array())))).unwrap()
+}
@@ -0,0 +1,116 @@
+extern crate std;
+
+use soroban_sdk::{testutils::Address as _, Address, Env};
+use std::vec::Vec;
+
+use crate::{StellarAssetClient, TokenClient};
+
+/// The amount minted to the account benchmarked by `benchmark_token_ops`
+const BENCHMARK_MINT: i128 = 1_0000000;
+
+/// The CPU instructions metered by each operation run by `benchmark_token_ops`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TokenBenchmark {
+    pub mint_cpu_instructions: u64,
+    pub balance_cpu_instructions: u64,
+    pub transfer_cpu_instructions: u64,
+    pub approve_cpu_instructions: u64,
+    pub transfer_from_cpu_instructions: u64,
+    pub burn_cpu_instructions: u64,
+    pub burn_from_cpu_instructions: u64,
+}
+
+/// Measure the CPU instructions metered by a single invocation of each operation of
+/// `token`, run in order between newly generated accounts.
+///
+/// All authorizations are mocked, and `mint` is invoked through `StellarAssetClient`.
+/// Operations are invoked with small amounts, so costs that scale with the size of a
+/// balance or allowance are not covered. Instructions are only representative of the
+/// network if `token` is deployed as Wasm, as natively registered contracts do not meter
+/// VM execution.
+pub fn benchmark_token_ops(env: &Env, token: &Address) -> TokenBenchmark {
+    env.mock_all_auths();
+    let client = TokenClient::new(env, token);
+    let from = Address::generate(env);
+    let to = Address::generate(env);
+    let spender = Address::generate(env);
+    // the budget is reset before every top level invocation
+    let cpu_instructions = || env.cost_estimate().budget().cpu_instruction_cost();
+
+    StellarAssetClient::new(env, token).mint(&from, &BENCHMARK_MINT);
+    let mint_cpu_instructions = cpu_instructions();
+    client.balance(&from);
+    let balance_cpu_instructions = cpu_instructions();
+    client.transfer(&from, &to, &100);
+    let transfer_cpu_instructions = cpu_instructions();
+    let live_until_ledger = env.ledger().sequence() + 100;
+    client.approve(&from, &spender, &200, &live_until_ledger);
+    let approve_cpu_instructions = cpu_instructions();
+    client.transfer_from(&spender, &from, &to, &100);
+    let transfer_from_cpu_instructions = cpu_instructions();
+    client.burn(&from, &100);
+    let burn_cpu_instructions = cpu_instructions();
+    client.burn_from(&spender, &from, &100);
+    let burn_from_cpu_instructions = cpu_instructions();
+
+    TokenBenchmark {
+        mint_cpu_instructions,
+        balance_cpu_instructions,
+        transfer_cpu_instructions,
+        approve_cpu_instructions,
+        transfer_from_cpu_instructions,
+        burn_cpu_instructions,
+        burn_from_cpu_instructions,
+    }
+}
+
+/// The CPU instructions metered by the allowance queries of `stress_test_allowances`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AllowanceStress {
+    /// The instructions metered querying the allowance of the first spender approved
+    pub first_allowance_cpu_instructions: u64,
+    /// The instructions metered querying the allowance of the last spender approved
+    pub last_allowance_cpu_instructions: u64,
+    /// The instructions metered querying the allowances of every spender
+    pub total_allowance_cpu_instructions: u64,
+}
+
+/// Approve `n` newly generated spenders to spend from `from`, then measure the CPU
+/// instructions metered by querying the allowance of each.
+///
+/// All authorizations are mocked. The allowance of a spender should be stored under its
+/// own key, such that the cost of querying the last spender is about that of the first,
+/// rather than growing with `n`.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+/// - `from` - The address approving the spenders
+/// - `n` - The number of spenders to approve. Must be at least 1.
+pub fn stress_test_allowances(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    n: u32,
+) -> AllowanceStress {
+    env.mock_all_auths();
+    let client = TokenClient::new(env, token);
+    let live_until_ledger = env.ledger().sequence() + 100;
+    let spenders: Vec<Address> = (0..n).map(|_| Address::generate(env)).collect();
+    for spender in spenders.iter() {
+        client.approve(from, spender, &100, &live_until_ledger);
+    }
+
+    let mut stress = AllowanceStress::default();
+    for (i, spender) in spenders.iter().enumerate() {
+        client.allowance(from, spender);
+        // the budget is reset before every top level invocation
+        let cpu_instructions = env.cost_estimate().budget().cpu_instruction_cost();
+        if i == 0 {
+            stress.first_allowance_cpu_instructions = cpu_instructions;
+        }
+        stress.last_allowance_cpu_instructions = cpu_instructions;
+        stress.total_allowance_cpu_instructions += cpu_instructions;
+    }
+    stress
+}
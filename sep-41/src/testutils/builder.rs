@@ -0,0 +1,94 @@
+use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+
+use crate::{
+    testutils::{MockTokenClient, MockTokenConfig, MockTokenWASM},
+    TokenClient,
+};
+
+/// Builder for a mock token deployed with a custom extension configuration.
+///
+/// The token is configured by a single `initialize_with_config` call, so no
+/// authorization needs to be mocked to build it. Defaults to a generated admin,
+/// 7 decimals, no cap, no transfer fee, and no deauthorized addresses.
+pub struct TestTokenBuilder<'a> {
+    env: &'a Env,
+    admin: Option<Address>,
+    decimals: u32,
+    name: &'a str,
+    symbol: &'a str,
+    cap: i128,
+    fee_bps: u32,
+    blacklist: Vec<Address>,
+}
+
+impl<'a> TestTokenBuilder<'a> {
+    pub fn new(env: &'a Env) -> Self {
+        TestTokenBuilder {
+            env,
+            admin: None,
+            decimals: 7,
+            name: "name",
+            symbol: "symbol",
+            cap: i128::MAX,
+            fee_bps: 0,
+            blacklist: Vec::new(env),
+        }
+    }
+
+    pub fn with_admin(mut self, admin: Address) -> Self {
+        self.admin = Some(admin);
+        self
+    }
+
+    pub fn with_decimals(mut self, decimals: u32) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    pub fn with_name(mut self, name: &'a str) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn with_symbol(mut self, symbol: &'a str) -> Self {
+        self.symbol = symbol;
+        self
+    }
+
+    /// Set the maximum total supply of the token
+    pub fn with_cap(mut self, cap: i128) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Set the transfer fee in basis points, paid to the admin
+    pub fn with_fee_bps(mut self, fee_bps: u32) -> Self {
+        self.fee_bps = fee_bps;
+        self
+    }
+
+    /// Deauthorize `id`, such that it cannot send or receive tokens
+    pub fn with_blacklist(mut self, id: Address) -> Self {
+        self.blacklist.push_back(id);
+        self
+    }
+
+    /// Deploy and initialize the token, returning its address and client
+    pub fn build(self) -> (Address, TokenClient<'a>) {
+        let admin = self.admin.unwrap_or_else(|| Address::generate(self.env));
+        let address = self.env.register(MockTokenWASM, ());
+        MockTokenClient::new(self.env, &address).initialize_with_config(
+            &admin,
+            &self.decimals,
+            &String::from_str(self.env, self.name),
+            &String::from_str(self.env, self.symbol),
+            &MockTokenConfig {
+                cap: self.cap,
+                fee_bps: self.fee_bps,
+                blacklist: self.blacklist,
+            },
+        );
+        let client = TokenClient::new(self.env, &address);
+        (address, client)
+    }
+}
@@ -0,0 +1,49 @@
+use soroban_sdk::{testutils::Ledger, Env};
+
+/// The number of seconds between ledgers, as on the Stellar network
+const SECONDS_PER_LEDGER: u64 = 5;
+
+/// Deterministic control of the ledger in tests, such as of tokens that stream or vest
+/// balances over time.
+///
+/// Moving the clock changes both the ledger sequence and the ledger timestamp, by
+/// `SECONDS_PER_LEDGER` seconds per ledger, so tokens based on either stay in sync.
+pub struct MockClock {
+    pub env: Env,
+}
+
+impl MockClock {
+    pub fn new(env: &Env) -> Self {
+        MockClock { env: env.clone() }
+    }
+
+    /// Returns the current ledger sequence
+    pub fn current_ledger(&self) -> u32 {
+        self.env.ledger().sequence()
+    }
+
+    /// Move the clock forward by `ledgers` ledgers
+    pub fn advance(&self, ledgers: u32) {
+        self.set(self.current_ledger() + ledgers);
+    }
+
+    /// Move the clock to the ledger sequence `ledger`, which may be in the past
+    pub fn set(&self, ledger: u32) {
+        let current = self.current_ledger();
+        let timestamp = self.now_as_timestamp();
+        let timestamp = if ledger >= current {
+            timestamp + (ledger - current) as u64 * SECONDS_PER_LEDGER
+        } else {
+            timestamp.saturating_sub((current - ledger) as u64 * SECONDS_PER_LEDGER)
+        };
+        self.env.ledger().with_mut(|info| {
+            info.sequence_number = ledger;
+            info.timestamp = timestamp;
+        });
+    }
+
+    /// Returns the current ledger timestamp, in seconds since the Unix epoch
+    pub fn now_as_timestamp(&self) -> u64 {
+        self.env.ledger().timestamp()
+    }
+}
@@ -0,0 +1,340 @@
+extern crate std;
+
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, AuthorizedFunction, Events, Ledger},
+    Address, Env, IntoVal, Symbol, TryFromVal, Val,
+};
+use std::{format, string::String, vec::Vec};
+
+use crate::{client_ext::TokenClientExt, StellarAssetClient, TokenClient};
+
+/// Check that `transfer_from` decrements the allowance of `spender` by exactly the
+/// amount transferred, rather than zeroing or otherwise changing it. For tokens that
+/// charge a transfer fee, this is the amount before the fee is deducted.
+///
+/// Approves `spender` to spend 100 tokens from `from`, then transfers 40 of them to
+/// `to`. `from` must hold at least 40 tokens, and the env must mock authorization, such
+/// as with `env.mock_all_auths()`.
+///
+/// Returns a description of the failure if the token does not conform.
+pub fn check_transfer_from_decrements_allowance(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    spender: &Address,
+    to: &Address,
+) -> Result<(), String> {
+    let client = TokenClient::new(env, token);
+    let live_until_ledger = env.ledger().sequence() + 1000;
+    client
+        .approve_checked(from, spender, &100, &live_until_ledger)
+        .map_err(|error| format!("approve failed: {:?}", error))?;
+    client
+        .transfer_from_checked(spender, from, to, &40)
+        .map_err(|error| format!("transfer_from failed: {:?}", error))?;
+
+    let allowance = client.allowance(from, spender);
+    if allowance != 60 {
+        return Err(format!(
+            "allowance is {} after transferring 40 of 100, expected 60",
+            allowance
+        ));
+    }
+    Ok(())
+}
+
+/// The outcome of a single conformance test
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConformanceResult {
+    pub name: &'static str,
+    /// A description of the failure, if the token does not conform
+    pub result: Result<(), String>,
+}
+
+impl ConformanceResult {
+    pub fn passed(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Run a fixed set of behavioral tests of SEP-0041 against `token`, and return the
+/// outcome of each.
+///
+/// `admin` must be able to mint tokens via `StellarAssetExtension::mint`, and the env
+/// must mock authorization, such as with `env.mock_all_auths()`. The tests mint tokens
+/// to `test_account_a`, move and burn tokens of the test accounts, and advance the ledger
+/// sequence.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+/// - `admin` - The admin of the token
+/// - `test_account_a` - An address to mint tokens to and transfer from
+/// - `test_account_b` - An address to transfer to
+pub fn run_sep41_conformance_tests(
+    env: &Env,
+    token: Address,
+    admin: Address,
+    test_account_a: Address,
+    test_account_b: Address,
+) -> Vec<ConformanceResult> {
+    let a = &test_account_a;
+    let b = &test_account_b;
+    let mut results = Vec::new();
+    let setup = match StellarAssetClient::new(env, &token).try_mint(a, &1000) {
+        Ok(Ok(())) if env.auths().iter().any(|(address, _)| *address == admin) => Ok(()),
+        Ok(Ok(())) => Err(String::from("mint was not authorized by admin")),
+        error => Err(format!("mint failed: {:?}", error)),
+    };
+    results.push(ConformanceResult {
+        name: "setup",
+        result: setup.clone(),
+    });
+    if setup.is_err() {
+        return results;
+    }
+
+    let spender = Address::generate(env);
+    results.push(ConformanceResult {
+        name: "metadata",
+        result: check_metadata(env, &token),
+    });
+    results.push(ConformanceResult {
+        name: "zero_balance",
+        result: check_zero_balance(env, &token),
+    });
+    results.push(ConformanceResult {
+        name: "transfer",
+        result: check_transfer(env, &token, a, b),
+    });
+    results.push(ConformanceResult {
+        name: "transfer_auth",
+        result: check_transfer_auth(env, &token, a, b),
+    });
+    results.push(ConformanceResult {
+        name: "transfer_event",
+        result: check_transfer_event(env, &token, a, b),
+    });
+    results.push(ConformanceResult {
+        name: "transfer_from_decrements_allowance",
+        result: check_transfer_from_decrements_allowance(env, &token, a, &spender, b),
+    });
+    results.push(ConformanceResult {
+        name: "burn_from_event",
+        result: check_burn_from_event(env, &token, a, &spender),
+    });
+    results.push(ConformanceResult {
+        name: "burn_auth",
+        result: check_burn_auth(env, &token, a),
+    });
+    results.push(ConformanceResult {
+        name: "revoke_missing_allowance",
+        result: check_revoke_missing_allowance(env, &token, a),
+    });
+    results.push(ConformanceResult {
+        name: "expired_allowance",
+        result: check_expired_allowance(env, &token, a, &spender),
+    });
+    results
+}
+
+/// Check that `decimals`, `name`, and `symbol` can be queried
+fn check_metadata(env: &Env, token: &Address) -> Result<(), String> {
+    let client = TokenClient::new(env, token);
+    if !matches!(client.try_decimals(), Ok(Ok(_))) {
+        return Err(String::from("decimals failed"));
+    }
+    if !matches!(client.try_name(), Ok(Ok(_))) {
+        return Err(String::from("name failed"));
+    }
+    if !matches!(client.try_symbol(), Ok(Ok(_))) {
+        return Err(String::from("symbol failed"));
+    }
+    Ok(())
+}
+
+/// Check that an address that never held tokens has a balance of 0
+fn check_zero_balance(env: &Env, token: &Address) -> Result<(), String> {
+    match TokenClient::new(env, token).try_balance(&Address::generate(env)) {
+        Ok(Ok(0)) => Ok(()),
+        Ok(Ok(balance)) => Err(format!("new address has balance {}, expected 0", balance)),
+        error => Err(format!("balance failed: {:?}", error)),
+    }
+}
+
+/// Check that `transfer` moves exactly the amount transferred
+fn check_transfer(env: &Env, token: &Address, from: &Address, to: &Address) -> Result<(), String> {
+    let client = TokenClient::new(env, token);
+    let from_balance = client.balance(from);
+    let to_balance = client.balance(to);
+    client
+        .transfer_checked(from, to, &100)
+        .map_err(|error| format!("transfer failed: {:?}", error))?;
+
+    let (from_after, to_after) = (client.balance(from), client.balance(to));
+    if from_after != from_balance - 100 || to_after != to_balance + 100 {
+        return Err(format!(
+            "balances are ({}, {}) after transferring 100 from ({}, {})",
+            from_after, to_after, from_balance, to_balance
+        ));
+    }
+    Ok(())
+}
+
+/// Check that `transfer` requires the authorization of `from`
+fn check_transfer_auth(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+) -> Result<(), String> {
+    TokenClient::new(env, token)
+        .transfer_checked(from, to, &10)
+        .map_err(|error| format!("transfer failed: {:?}", error))?;
+
+    let args = (from.clone(), to.clone(), 10_i128).into_val(env);
+    if !was_authorized(env, token, from, "transfer", args) {
+        return Err(String::from("transfer did not require the auth of from"));
+    }
+    Ok(())
+}
+
+/// Check that `transfer` emits a `transfer` event with the amount transferred
+fn check_transfer_event(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+) -> Result<(), String> {
+    TokenClient::new(env, token)
+        .transfer_checked(from, to, &10)
+        .map_err(|error| format!("transfer failed: {:?}", error))?;
+
+    let topics: soroban_sdk::Vec<Val> =
+        (symbol_short!("transfer"), from.clone(), to.clone()).into_val(env);
+    let emitted = env
+        .events()
+        .all()
+        .iter()
+        .any(|(address, event_topics, data)| {
+            address == *token && event_topics == topics && i128::try_from_val(env, &data) == Ok(10)
+        });
+    if !emitted {
+        return Err(String::from(
+            "no [\"transfer\", from, to] event with data 10 was emitted",
+        ));
+    }
+    Ok(())
+}
+
+/// Check that `burn_from` emits a single `burn` event, and no `approve` event for the
+/// decremented allowance, consistent with `transfer_from`
+fn check_burn_from_event(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    spender: &Address,
+) -> Result<(), String> {
+    let client = TokenClient::new(env, token);
+    let live_until_ledger = env.ledger().sequence() + 1000;
+    client
+        .approve_checked(from, spender, &100, &live_until_ledger)
+        .map_err(|error| format!("approve failed: {:?}", error))?;
+    client
+        .burn_from_checked(spender, from, &10)
+        .map_err(|error| format!("burn_from failed: {:?}", error))?;
+
+    let topics: soroban_sdk::Vec<Val> = (symbol_short!("burn"), from.clone()).into_val(env);
+    let events: Vec<_> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(address, _, _)| address == token)
+        .collect();
+    match events.as_slice() {
+        [(_, event_topics, data)]
+            if *event_topics == topics && i128::try_from_val(env, data) == Ok(10) =>
+        {
+            Ok(())
+        }
+        [_] => Err(String::from(
+            "burn_from did not emit a [\"burn\", from] event with data 10",
+        )),
+        _ => Err(format!(
+            "burn_from emitted {} events, expected 1",
+            events.len()
+        )),
+    }
+}
+
+/// Check that `burn` requires the authorization of `from`
+fn check_burn_auth(env: &Env, token: &Address, from: &Address) -> Result<(), String> {
+    TokenClient::new(env, token)
+        .burn_checked(from, &10)
+        .map_err(|error| format!("burn failed: {:?}", error))?;
+
+    let args = (from.clone(), 10_i128).into_val(env);
+    if !was_authorized(env, token, from, "burn", args) {
+        return Err(String::from("burn did not require the auth of from"));
+    }
+    Ok(())
+}
+
+/// Check that revoking an allowance that was never set succeeds, even with an expired
+/// live until ledger
+fn check_revoke_missing_allowance(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+) -> Result<(), String> {
+    let client = TokenClient::new(env, token);
+    let spender = Address::generate(env);
+    client
+        .approve_checked(from, &spender, &0, &0)
+        .map_err(|error| format!("revoking a missing allowance failed: {:?}", error))?;
+
+    let allowance = client.allowance(from, &spender);
+    if allowance != 0 {
+        return Err(format!("revoked allowance is {}, expected 0", allowance));
+    }
+    Ok(())
+}
+
+/// Check that an allowance reads as 0 once its live until ledger has passed
+fn check_expired_allowance(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    spender: &Address,
+) -> Result<(), String> {
+    let client = TokenClient::new(env, token);
+    let live_until_ledger = env.ledger().sequence();
+    client
+        .approve_checked(from, spender, &100, &live_until_ledger)
+        .map_err(|error| format!("approve failed: {:?}", error))?;
+    env.ledger().set_sequence_number(live_until_ledger + 1);
+
+    let allowance = client.allowance(from, spender);
+    if allowance != 0 {
+        return Err(format!("expired allowance is {}, expected 0", allowance));
+    }
+    Ok(())
+}
+
+/// Returns true if `address` authorized a call of `function` on `token` with `args` in the
+/// last invocation
+fn was_authorized(
+    env: &Env,
+    token: &Address,
+    address: &Address,
+    function: &str,
+    args: soroban_sdk::Vec<Val>,
+) -> bool {
+    let function = Symbol::new(env, function);
+    env.auths().iter().any(|(authorizer, invocation)| {
+        authorizer == address
+            && invocation.function
+                == AuthorizedFunction::Contract((token.clone(), function.clone(), args.clone()))
+    })
+}
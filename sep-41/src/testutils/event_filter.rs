@@ -0,0 +1,28 @@
+use soroban_sdk::{Address, Val, Vec};
+
+use crate::events::EventFilter;
+
+/// Select the events of an event log that match `filter`, such as the events returned by
+/// `env.events().all()`.
+///
+/// The test event log only holds the events of the most recent invocation, so every
+/// event is treated as published in the current ledger.
+///
+/// # Arguments
+///
+/// - `events` - The event log, as `(contract, topics, data)` tuples
+/// - `filter` - The criteria the returned events match
+pub fn apply_filter(
+    events: Vec<(Address, Vec<Val>, Val)>,
+    filter: &EventFilter,
+) -> Vec<(Address, Vec<Val>, Val)> {
+    let env = events.env().clone();
+    let ledger = env.ledger().sequence();
+    let mut matched = Vec::new(&env);
+    for event in events.iter() {
+        if filter.matches(&env, ledger, &event.1) {
+            matched.push_back(event);
+        }
+    }
+    matched
+}
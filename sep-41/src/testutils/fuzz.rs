@@ -0,0 +1,94 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{
+    testutils::{InvariantChecker, TokenInvariants},
+    StellarAssetClient, TokenClient,
+};
+
+/// The largest amount minted in a single fuzzed operation, of 1M tokens with 7 decimals
+const MAX_FUZZ_MINT: u64 = 10_000_000_000_000;
+
+/// A xorshift generator, such that fuzzed sequences are reproducible
+struct Xorshift(u64);
+
+impl Xorshift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a value in `[0, bound)`
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+
+    fn account<'a>(&mut self, accounts: &'a [Address]) -> &'a Address {
+        &accounts[self.below(accounts.len() as u64) as usize]
+    }
+
+    /// Returns an amount up to twice `balance`, such that some operations fail
+    fn amount(&mut self, balance: i128) -> i128 {
+        let bound = (balance.max(0) as u64).saturating_mul(2).saturating_add(1);
+        self.below(bound) as i128
+    }
+}
+
+/// Run `iterations` random `transfer`, `approve`, `transfer_from`, `mint`, and `burn`
+/// operations against `token` between `accounts`, checking `TokenInvariants` after each.
+///
+/// All authorizations are mocked, and `mint` is invoked through `StellarAssetClient`.
+/// Operations that fail are expected, as random amounts can exceed balances and
+/// allowances. Invariant failures panic.
+///
+/// The invariants are seeded with the sum of the current balances of `accounts` as the
+/// total supply.
+pub fn fuzz_token(env: &Env, token: &Address, accounts: &[Address], iterations: u32) {
+    if accounts.is_empty() {
+        panic!("fuzzing requires at least one account");
+    }
+    env.mock_all_auths();
+    let client = TokenClient::new(env, token);
+    let admin_client = StellarAssetClient::new(env, token);
+    let mut invariants = TokenInvariants::new(token, accounts);
+    for account in accounts.iter() {
+        invariants.mint(client.balance(account));
+    }
+    invariants.check_invariants(env);
+
+    let mut rng = Xorshift(0x5eed_0041 ^ ((iterations as u64) << 32));
+    for _ in 0..iterations {
+        let from = rng.account(accounts);
+        let to = rng.account(accounts);
+        match rng.below(5) {
+            0 => {
+                let amount = rng.amount(client.balance(from));
+                let _ = client.try_transfer(from, to, &amount);
+            }
+            1 => {
+                let amount = rng.amount(client.balance(from));
+                let live_until_ledger = env.ledger().sequence() + rng.below(100) as u32;
+                let _ = client.try_approve(from, to, &amount, &live_until_ledger);
+            }
+            2 => {
+                let spender = rng.account(accounts);
+                let amount = rng.amount(client.allowance(from, spender));
+                let _ = client.try_transfer_from(spender, from, to, &amount);
+            }
+            3 => {
+                let amount = rng.below(MAX_FUZZ_MINT) as i128;
+                if let Ok(Ok(())) = admin_client.try_mint(to, &amount) {
+                    invariants.mint(amount);
+                }
+            }
+            _ => {
+                let amount = rng.amount(client.balance(from));
+                if let Ok(Ok(())) = client.try_burn(from, &amount) {
+                    invariants.burn(amount);
+                }
+            }
+        }
+        invariants.check_invariants(env);
+    }
+}
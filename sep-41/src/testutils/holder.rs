@@ -0,0 +1,14 @@
+use soroban_sdk::{contract, contractimpl, Address, Env};
+
+/// A contract without any logic, that only holds tokens sent to it
+#[contract]
+pub struct TokenHoldingContract;
+
+#[contractimpl]
+impl TokenHoldingContract {}
+
+/// Deploy a contract that can hold tokens, such as to test functions where the token
+/// holder is a contract rather than an account. Returns the address of the contract.
+pub fn create_token_holding_contract(env: &Env) -> Address {
+    env.register(TokenHoldingContract, ())
+}
@@ -0,0 +1,86 @@
+extern crate std;
+
+use soroban_sdk::{Address, Env};
+use std::vec::Vec;
+
+use crate::TokenClient;
+
+/// A set of invariants that can be checked after every operation performed against a token
+pub trait InvariantChecker {
+    /// Assert that all invariants hold.
+    ///
+    /// Panics with a message describing the invariant that failed.
+    fn check_invariants(&self, env: &Env);
+}
+
+/// Shadow registry of the accounts and total supply of a SEP-0041 token under test.
+///
+/// SEP-0041 does not expose the total supply or the set of holders, so both are tracked
+/// by the test as it mints and burns tokens.
+///
+/// Checks that:
+/// 1. No tracked account has a negative balance
+/// 2. The sum of all tracked balances does not exceed the total supply
+/// 3. No allowance between tracked accounts is negative
+///
+/// Allowances are not compared against the holder's balance, as SEP-0041 allows an
+/// allowance to exceed the balance it is drawn from.
+pub struct TokenInvariants {
+    pub token: Address,
+    pub accounts: Vec<Address>,
+    pub total_supply: i128,
+}
+
+impl TokenInvariants {
+    /// Create a shadow registry for `token` tracking `accounts`, with a total supply of 0.
+    pub fn new(token: &Address, accounts: &[Address]) -> Self {
+        TokenInvariants {
+            token: token.clone(),
+            accounts: accounts.to_vec(),
+            total_supply: 0,
+        }
+    }
+
+    /// Record that `amount` tokens were minted
+    pub fn mint(&mut self, amount: i128) {
+        self.total_supply += amount;
+    }
+
+    /// Record that `amount` tokens were burned
+    pub fn burn(&mut self, amount: i128) {
+        self.total_supply -= amount;
+    }
+}
+
+impl InvariantChecker for TokenInvariants {
+    fn check_invariants(&self, env: &Env) {
+        let client = TokenClient::new(env, &self.token);
+        let mut balance_sum: i128 = 0;
+        for account in self.accounts.iter() {
+            let balance = client.balance(account);
+            if balance < 0 {
+                panic!(
+                    "invariant failed: negative balance of {} for {:?}",
+                    balance, account
+                );
+            }
+            balance_sum += balance;
+
+            for spender in self.accounts.iter() {
+                let allowance = client.allowance(account, spender);
+                if allowance < 0 {
+                    panic!(
+                        "invariant failed: negative allowance of {} from {:?} to {:?}",
+                        allowance, account, spender
+                    );
+                }
+            }
+        }
+        if balance_sum > self.total_supply {
+            panic!(
+                "invariant failed: sum of balances {} exceeds total supply {}",
+                balance_sum, self.total_supply
+            );
+        }
+    }
+}
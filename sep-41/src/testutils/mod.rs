@@ -2,6 +2,32 @@ mod wasm {
     soroban_sdk::contractimport!(file = "./src/testutils/mock_sep_41_token.wasm");
 }
 
+mod address_book;
+mod benchmark;
+mod builder;
+mod clock;
+mod conformance;
+mod event_filter;
+mod fuzz;
+mod holder;
+mod invariants;
+mod scenario;
+mod snapshot;
+
+pub use address_book::{named_address, TokenAddressBook};
+pub use benchmark::{benchmark_token_ops, stress_test_allowances, AllowanceStress, TokenBenchmark};
+pub use builder::TestTokenBuilder;
+pub use clock::MockClock;
+pub use conformance::{
+    check_transfer_from_decrements_allowance, run_sep41_conformance_tests, ConformanceResult,
+};
+pub use event_filter::apply_filter;
+pub use fuzz::fuzz_token;
+pub use holder::{create_token_holding_contract, TokenHoldingContract};
+pub use invariants::{InvariantChecker, TokenInvariants};
+pub use scenario::{TestScenario, SCENARIO_BALANCE};
+pub use snapshot::{token_snapshot_diff, BalanceDelta, TokenSnapshot};
 pub use wasm::{
-    Client as MockTokenClient, Contract as MockToken, TokenError, WASM as MockTokenWASM,
+    Client as MockTokenClient, Contract as MockToken, MockTokenConfig, TokenError,
+    WASM as MockTokenWASM,
 };
@@ -0,0 +1,67 @@
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+use crate::testutils::{MockTokenClient, TestTokenBuilder};
+
+/// The amount minted to each user of a `TestScenario`
+pub const SCENARIO_BALANCE: i128 = 1000;
+
+/// A mock token with 3 users holding `SCENARIO_BALANCE` each, for tests that need a
+/// common starting state.
+///
+/// Minting requires authorization by `admin`, so the env must mock authorization, such
+/// as with `env.mock_all_auths()`.
+pub struct TestScenario<'a> {
+    pub token: MockTokenClient<'a>,
+    pub admin: Address,
+    pub user1: Address,
+    pub user2: Address,
+    pub user3: Address,
+    /// An address with no balance, which only has an allowance in `with_allowance`
+    pub spender: Address,
+}
+
+impl<'a> TestScenario<'a> {
+    /// A token administered by `admin`, where each user holds `SCENARIO_BALANCE`
+    pub fn simple(env: &'a Env, admin: &Address) -> Self {
+        let (address, _) = TestTokenBuilder::new(env).with_admin(admin.clone()).build();
+        let token = MockTokenClient::new(env, &address);
+        let users = [
+            Address::generate(env),
+            Address::generate(env),
+            Address::generate(env),
+        ];
+        for user in users.iter() {
+            token.mint(user, &SCENARIO_BALANCE);
+        }
+        let [user1, user2, user3] = users;
+        TestScenario {
+            token,
+            admin: admin.clone(),
+            user1,
+            user2,
+            user3,
+            spender: Address::generate(env),
+        }
+    }
+
+    /// The `simple` scenario, where `spender` has an allowance of `spender_amount` from
+    /// `user1` that lives until the maximum ledger
+    pub fn with_allowance(env: &'a Env, admin: &Address, spender_amount: i128) -> Self {
+        let scenario = Self::simple(env, admin);
+        scenario.token.approve(
+            &scenario.user1,
+            &scenario.spender,
+            &spender_amount,
+            &env.ledger().max_live_until_ledger(),
+        );
+        scenario
+    }
+
+    /// The `simple` scenario, where `user3` is deauthorized and cannot send or receive
+    /// tokens
+    pub fn with_frozen_account(env: &'a Env, admin: &Address) -> Self {
+        let scenario = Self::simple(env, admin);
+        scenario.token.set_authorized(&scenario.user3, &false);
+        scenario
+    }
+}
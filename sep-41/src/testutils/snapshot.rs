@@ -0,0 +1,59 @@
+extern crate std;
+
+use soroban_sdk::{Address, Env};
+use std::vec::Vec;
+
+use crate::TokenClient;
+
+/// A change in the balance of an address, as `(address, balance before, balance after)`
+pub type BalanceDelta = (Address, i128, i128);
+
+/// The balances of a set of accounts of a SEP-0041 token at a point in a test
+pub struct TokenSnapshot {
+    pub token: Address,
+    pub balances: Vec<(Address, i128)>,
+}
+
+impl TokenSnapshot {
+    /// Capture the balance of each of `accounts` for `token`
+    pub fn capture(env: &Env, token: &Address, accounts: &[Address]) -> Self {
+        let client = TokenClient::new(env, token);
+        TokenSnapshot {
+            token: token.clone(),
+            balances: accounts
+                .iter()
+                .map(|account| (account.clone(), client.balance(account)))
+                .collect(),
+        }
+    }
+}
+
+/// Returns the balances that differ between `before` and `after`, in the order the
+/// accounts were captured in `after`.
+///
+/// Accounts only captured in one snapshot are treated as having a balance of 0 in
+/// the other.
+pub fn token_snapshot_diff(before: &TokenSnapshot, after: &TokenSnapshot) -> Vec<BalanceDelta> {
+    let balance_of = |snapshot: &TokenSnapshot, account: &Address| {
+        snapshot
+            .balances
+            .iter()
+            .find(|(id, _)| id == account)
+            .map_or(0, |(_, balance)| *balance)
+    };
+
+    let mut deltas = Vec::new();
+    for (account, balance) in after.balances.iter() {
+        let prev = balance_of(before, account);
+        if prev != *balance {
+            deltas.push((account.clone(), prev, *balance));
+        }
+    }
+    for (account, balance) in before.balances.iter() {
+        let captured = after.balances.iter().any(|(id, _)| id == account);
+        if !captured && *balance != 0 {
+            deltas.push((account.clone(), *balance, 0));
+        }
+    }
+    deltas
+}
@@ -0,0 +1,299 @@
+//! Helper functions for contracts interacting with SEP-0041 tokens
+
+use soroban_sdk::{
+    contracttype, panic_with_error, unwrap::UnwrapOptimized, xdr::ScErrorType, Address, BytesN,
+    Env, Executable, Map, Symbol,
+};
+
+use crate::{
+    CappedTokenClient, EnumerableHoldersTokenClient, StellarAssetClient, TokenClient, TokenEvents,
+    TransferReceipt,
+};
+
+/// The maximum number of characters a `Symbol` can hold
+const MAX_SYMBOL_LEN: usize = 32;
+
+/// Fetch the symbol of `token` as a `Symbol`, for use as a map key or event topic.
+///
+/// Panics if the token's symbol is longer than 32 characters or contains characters
+/// outside of `[a-zA-Z0-9_]`, as it cannot be represented as a `Symbol`.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+pub fn symbol_key(env: &Env, token: &Address) -> Symbol {
+    let symbol = TokenClient::new(env, token).symbol();
+    let len = symbol.len() as usize;
+    if len > MAX_SYMBOL_LEN {
+        panic!("token symbol is too long to be a Symbol");
+    }
+    let mut buf = [0u8; MAX_SYMBOL_LEN];
+    symbol.copy_into_slice(&mut buf[..len]);
+    Symbol::new(env, core::str::from_utf8(&buf[..len]).unwrap_optimized())
+}
+
+/// Check if `address` appears to be a SEP-0041 token by invoking `decimals` and
+/// checking that it returns a valid value without reverting.
+///
+/// This is a best-effort check and is non-exhaustive. A contract that implements
+/// `decimals` is not guaranteed to implement the rest of the SEP-0041 interface.
+///
+/// # Arguments
+///
+/// - `address` - The address to check
+pub fn is_sep41_token(env: &Env, address: &Address) -> bool {
+    matches!(TokenClient::new(env, address).try_decimals(), Ok(Ok(_)))
+}
+
+/// Check if `token` is a custom contract token, rather than a Stellar Asset Contract
+/// wrapping a classic asset. Returns false if `token` is not a contract.
+///
+/// Unlike a Stellar Asset Contract, a contract token is not guaranteed to implement the
+/// admin functions of `StellarAssetExtension`, such as `clawback`.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+pub fn is_contract_token(token: &Address) -> bool {
+    matches!(token.executable(), Some(Executable::Wasm(_)))
+}
+
+/// Check if `id` is authorized to use `token`.
+///
+/// Tokens that implement authorization flags via `StellarAssetExtension::authorized` are
+/// queried for the status of `id`. Tokens that do not are assumed to authorize every
+/// address, so this only returns false if the token reports `id` as unauthorized.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+/// - `id` - The address for which authorization is being queried
+pub fn is_authorized(env: &Env, token: &Address, id: &Address) -> bool {
+    !matches!(
+        StellarAssetClient::new(env, token).try_authorized(id),
+        Ok(Ok(false))
+    )
+}
+
+/// Set the authorization status of `id` to `authorize` from a token implementation,
+/// only if it changes, such that setting the same status again does not emit a
+/// `set_authorized` event.
+///
+/// Returns true if the status changed, in which case `write_authorized` is called to
+/// store it and the `set_authorized` event is emitted.
+///
+/// # Arguments
+///
+/// - `admin` - The admin setting the authorization status
+/// - `id` - The address whose authorization status is being set
+/// - `current` - The current authorization status of `id`
+/// - `authorize` - The new authorization status of `id`
+/// - `write_authorized` - Stores the new authorization status of `id`
+pub fn set_authorized_idempotent(
+    env: &Env,
+    admin: &Address,
+    id: &Address,
+    current: bool,
+    authorize: bool,
+    write_authorized: impl FnOnce(&Env, &Address, bool),
+) -> bool {
+    if current == authorize {
+        return false;
+    }
+    write_authorized(env, id, authorize);
+    TokenEvents::set_authorized(env, admin.clone(), id.clone(), authorize);
+    true
+}
+
+/// Transfer `amount` of `token` from `from` to `to`, only if `to` is eligible to receive
+/// the token. Returns true if the transfer was made, or false if `to` is ineligible, in
+/// which case the tokens are left with `from`.
+///
+/// `to` is ineligible if `is_authorized` reports it unauthorized, or if the transfer
+/// fails with a contract error, such as a recipient the token does not permit to
+/// receive. The failed transfer is rolled back. Panics if the transfer fails with any
+/// other error, such as missing authorization from `from`.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+/// - `from` - The address holding the balance of tokens which will be withdrawn from
+/// - `to` - The address which will receive the transferred tokens
+/// - `amount` - The amount of tokens to be transferred
+pub fn refund_transfer(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+) -> bool {
+    if !is_authorized(env, token, to) {
+        return false;
+    }
+    match TokenClient::new(env, token).try_transfer(from, to, &amount) {
+        Ok(_) => true,
+        Err(Ok(error)) if !error.is_type(ScErrorType::Contract) => panic_with_error!(env, error),
+        Err(_) => false,
+    }
+}
+
+/// Fetch the maximum total supply of `token`, if it has one.
+///
+/// Tokens that implement `CappedToken` are queried for their cap. Returns `None` if the
+/// token does not implement `CappedToken`, or reports a cap of `i128::MAX`, which places
+/// no limit on the supply.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+pub fn supply_cap(env: &Env, token: &Address) -> Option<i128> {
+    match CappedTokenClient::new(env, token).try_cap() {
+        Ok(Ok(cap)) if cap != i128::MAX => Some(cap),
+        _ => None,
+    }
+}
+
+/// Compute the total supply of `token` by summing the balances of its holders, for
+/// auditing tokens that do not track their supply or checking that a tracked supply is
+/// consistent with the balances.
+///
+/// This is expensive, as it reads the balance of every holder, and is intended for
+/// read-only diagnostics rather than for use in transactions. Returns `None` if the token
+/// does not implement `EnumerableHoldersToken`, or has more than
+/// `max_holders_for_lazy_supply` holders.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+/// - `max_holders_for_lazy_supply` - The maximum number of holders to read
+pub fn lazy_total_supply(
+    env: &Env,
+    token: &Address,
+    max_holders_for_lazy_supply: u32,
+) -> Option<i128> {
+    let page_size = max_holders_for_lazy_supply.saturating_add(1);
+    match EnumerableHoldersTokenClient::new(env, token).try_holders(&0, &page_size) {
+        Ok(Ok(holders)) if holders.len() <= max_holders_for_lazy_supply => {
+            Some(holders.iter().map(|(_, balance)| balance).sum())
+        }
+        _ => None,
+    }
+}
+
+/// Compute a 4 byte fingerprint of `token`, such that users can confirm out-of-band that
+/// an address refers to the token they expect.
+///
+/// The checksum is the first 4 bytes of `sha256(address || name || symbol || decimals)`,
+/// where `address` is the strkey of the token and `decimals` is a big-endian `u32`. As the
+/// checksum is only 32 bits, it detects copy-paste errors but is not collision resistant.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+pub fn token_address_checksum(env: &Env, token: &Address) -> BytesN<4> {
+    let client = TokenClient::new(env, token);
+    let mut preimage = token.to_string().to_bytes();
+    preimage.append(&client.name().to_bytes());
+    preimage.append(&client.symbol().to_bytes());
+    preimage.extend_from_array(&client.decimals().to_be_bytes());
+
+    let hash = env.crypto().sha256(&preimage).to_array();
+    BytesN::from_array(env, &[hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// A pair of tokens, such as the assets of a liquidity pool
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TokenPair {
+    pub token_a: Address,
+    pub token_b: Address,
+}
+
+/// Approve the invoking contract to spend `amount_a` of `pair.token_a` and
+/// `amount_b` of `pair.token_b` held by `owner`.
+///
+/// Both approvals require authorization by `owner`, and can be signed as part of the
+/// same authorization for the invocation of the current contract.
+///
+/// # Arguments
+///
+/// - `pair` - The tokens to be approved
+/// - `owner` - The address holding the balance of tokens to be drawn from
+/// - `amount_a` - The amount of `pair.token_a` to be approved
+/// - `amount_b` - The amount of `pair.token_b` to be approved
+/// - `live_until_ledger` - The ledger number where both allowances expire
+pub fn create_pair_allowance(
+    env: &Env,
+    pair: &TokenPair,
+    owner: Address,
+    amount_a: i128,
+    amount_b: i128,
+    live_until_ledger: u32,
+) {
+    let spender = env.current_contract_address();
+    TokenClient::new(env, &pair.token_a).approve(&owner, &spender, &amount_a, &live_until_ledger);
+    TokenClient::new(env, &pair.token_b).approve(&owner, &spender, &amount_b, &live_until_ledger);
+}
+
+/// Approve `spender` to spend an unlimited amount of `token` held by `from`, by setting
+/// an allowance of `i128::MAX`.
+///
+/// Requires authorization by `from`.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+/// - `from` - The address holding the balance of tokens to be drawn from
+/// - `spender` - The address being authorized to spend the tokens held by `from`
+/// - `live_until_ledger` - The ledger number where the allowance expires
+pub fn approve_max(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    spender: &Address,
+    live_until_ledger: u32,
+) {
+    TokenClient::new(env, token).approve(from, spender, &i128::MAX, &live_until_ledger);
+}
+
+/// Revoke the allowance of `spender` to spend `token` held by `from`, by setting an
+/// allowance of 0 with a `live_until_ledger` of 0.
+///
+/// SEP-0041 allows an expired `live_until_ledger` when the amount is 0, and a zero
+/// allowance is equivalent to no allowance, so implementations may delete the
+/// allowance entry entirely.
+///
+/// Requires authorization by `from`.
+///
+/// # Arguments
+///
+/// - `token` - The address of the token
+/// - `from` - The address holding the balance of tokens to be drawn from
+/// - `spender` - The address whose allowance is being revoked
+pub fn revoke_approval(env: &Env, token: &Address, from: &Address, spender: &Address) {
+    TokenClient::new(env, token).approve(from, spender, &0, &0);
+}
+
+/// Compute the net change in balance of each address from a batch of transfers, where
+/// each transfer is `(from, to, receipt)`.
+///
+/// `from` is debited the `gross_amount` and `to` is credited the `net_amount` of each
+/// receipt. A `TransferReceipt` does not identify where its `fee` went, so fees are
+/// not credited to any address, and the deltas sum to the negated total of the fees.
+///
+/// # Arguments
+///
+/// - `receipts` - The sender, recipient, and receipt of each transfer
+pub fn aggregate_receipts(
+    env: &Env,
+    receipts: &[(Address, Address, TransferReceipt)],
+) -> Map<Address, i128> {
+    let mut deltas: Map<Address, i128> = Map::new(env);
+    for (from, to, receipt) in receipts.iter() {
+        let from_delta = deltas.get(from.clone()).unwrap_or(0) - receipt.gross_amount;
+        deltas.set(from.clone(), from_delta);
+        let to_delta = deltas.get(to.clone()).unwrap_or(0) + receipt.net_amount;
+        deltas.set(to.clone(), to_delta);
+    }
+    deltas
+}
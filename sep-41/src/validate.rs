@@ -0,0 +1,109 @@
+//! Guards for validating token configuration, for use by token implementations
+
+use soroban_sdk::{panic_with_error, Address, Env, Error, String};
+
+use crate::{fractional::MIN_FRACTIONAL_DECIMALS, MAX_DECIMALS};
+
+/// The strkey of the account with an all-zero public key.
+///
+/// Stellar has no canonical zero address, but contracts ported from other ecosystems
+/// often treat this account as one.
+pub const ZERO_ACCOUNT_ADDRESS: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+
+/// The strkey of the contract with an all-zero contract id.
+pub const ZERO_CONTRACT_ADDRESS: &str = "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABSC4";
+
+/// Panic with `error` if the decimals of a token can no longer be changed, as tokens
+/// have already been minted. Changing the decimals of a token with a supply would
+/// change the value of every stored amount.
+///
+/// # Arguments
+///
+/// - `total_supply` - The total supply of the token.
+/// - `error` - The error to panic with.
+pub fn require_decimals_mutable(env: &Env, total_supply: i128, error: impl Into<Error>) {
+    if total_supply > 0 {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Panic with `error` if `address` is `ZERO_ACCOUNT_ADDRESS` or `ZERO_CONTRACT_ADDRESS`.
+///
+/// SEP-0041 does not define a zero address, so tokens behave as they would for any other
+/// address when tokens are sent to one. Tokens that want to reject these addresses, such
+/// as to catch uninitialized values, can apply this guard to recipients.
+///
+/// # Arguments
+///
+/// - `address` - The address to check.
+/// - `error` - The error to panic with.
+pub fn validate_not_zero_address(env: &Env, address: &Address, error: impl Into<Error>) {
+    if *address == Address::from_str(env, ZERO_ACCOUNT_ADDRESS)
+        || *address == Address::from_str(env, ZERO_CONTRACT_ADDRESS)
+    {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Panic with `error` if `decimals` exceeds `MAX_DECIMALS`, for use when initializing a
+/// token or changing its decimals. `10^decimals` overflows an `i128` above this limit, so
+/// a single whole token could not be represented.
+///
+/// # Arguments
+///
+/// - `decimals` - The decimals of the token.
+/// - `error` - The error to panic with.
+pub fn validate_decimals(env: &Env, decimals: u32, error: impl Into<Error>) {
+    if decimals > MAX_DECIMALS {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Panic with `error` if `decimals` is too few to represent fractional ownership, for use
+/// when initializing a token.
+///
+/// # Arguments
+///
+/// - `decimals` - The decimals of the token. Cannot be less than
+///   `MIN_FRACTIONAL_DECIMALS`.
+/// - `error` - The error to panic with.
+pub fn require_fractional_decimals(env: &Env, decimals: u32, error: impl Into<Error>) {
+    if decimals < MIN_FRACTIONAL_DECIMALS {
+        panic_with_error!(env, error);
+    }
+}
+
+/// Returns true if `string` is valid UTF-8 without control characters (below `0x20`),
+/// such that it can be displayed as a token name or symbol.
+///
+/// # Arguments
+///
+/// - `string` - The string to check.
+pub fn validate_string_utf8(string: &String) -> bool {
+    let bytes = string.to_bytes();
+    let mut iter = bytes.iter();
+    while let Some(first) = iter.next() {
+        // the number of continuation bytes, and the smallest code point they can encode
+        let (continuation_len, min) = match first {
+            0x00..=0x1F => return false,
+            0x20..=0x7F => continue,
+            0xC2..=0xDF => (1, 0x80),
+            0xE0..=0xEF => (2, 0x800),
+            0xF0..=0xF4 => (3, 0x10000),
+            _ => return false,
+        };
+        let mut code_point = (first & (0x7F >> (continuation_len + 1))) as u32;
+        for _ in 0..continuation_len {
+            match iter.next() {
+                Some(byte) if byte & 0xC0 == 0x80 => {
+                    code_point = (code_point << 6) | (byte & 0x3F) as u32
+                }
+                _ => return false,
+            }
+        }
+        if code_point < min || code_point > 0x10FFFF || (0xD800..=0xDFFF).contains(&code_point) {
+            return false;
+        }
+    }
+    true
+}
@@ -0,0 +1,71 @@
+//! Interfaces for contracts that watch the balances of SEP-0041 tokens, and the contracts
+//! they alert when a balance falls below a threshold
+
+use soroban_sdk::{contractclient, contracttype, Address, Env};
+
+/// A request to alert `callback` once the balance of `account` falls below `threshold`
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Watch {
+    /// The address of the token being watched
+    pub token: Address,
+    /// The address whose balance is being watched
+    pub account: Address,
+    /// The balance below which `callback` is alerted
+    pub threshold: i128,
+    /// The contract implementing `WatcherCallback` to alert
+    pub callback: Address,
+    /// The last ledger number where the watch can trigger an alert
+    pub expiry: u32,
+}
+
+/// Token Watcher Trait
+#[contractclient(name = "TokenWatcherClient")]
+pub trait TokenWatcher {
+    /// Register `watch`, owned by `owner`.
+    ///
+    /// Requires authorization by `owner`. Returns the id of the watch.
+    ///
+    /// # Arguments
+    ///
+    /// - `owner` - The address that can cancel the watch.
+    /// - `watch` - The balance to watch and the contract to alert.
+    fn watch(env: Env, owner: Address, watch: Watch) -> u64;
+
+    /// Alert the callback of the watch `watch_id` if the balance of its account is below
+    /// its threshold, by invoking `WatcherCallback::on_threshold_breach`. Can be invoked
+    /// by anyone, such that bots can trigger alerts without polling on behalf of owners.
+    ///
+    /// Panics if the watch does not exist, has expired, or the balance is not below the
+    /// threshold.
+    ///
+    /// # Arguments
+    ///
+    /// - `watch_id` - The id of the watch, as returned by `watch`.
+    fn check_and_alert(env: Env, watch_id: u64);
+
+    /// Cancel the watch `watch_id`.
+    ///
+    /// Requires authorization by the owner of the watch.
+    ///
+    /// # Arguments
+    ///
+    /// - `watch_id` - The id of the watch, as returned by `watch`.
+    fn cancel_watch(env: Env, watch_id: u64);
+}
+
+/// Watcher Callback Trait
+#[contractclient(name = "WatcherCallbackClient")]
+pub trait WatcherCallback {
+    /// Called by a `TokenWatcher` when the balance of `account` was found to be below the
+    /// threshold of a watch registered for this contract.
+    ///
+    /// Any contract can invoke this function, so callbacks must not act on `balance`
+    /// without verifying it, e.g. by querying the token directly.
+    ///
+    /// # Arguments
+    ///
+    /// - `account` - The address whose balance fell below the threshold.
+    /// - `balance` - The balance of `account` when the alert was triggered.
+    fn on_threshold_breach(env: Env, account: Address, balance: i128);
+}